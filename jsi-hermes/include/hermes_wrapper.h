@@ -0,0 +1,108 @@
+#pragma once
+#include <hermes/hermes.h>
+#include <hermes/Public/RuntimeConfig.h>
+#include <jsi/jsi.h>
+#include <ostream>
+#include "rust/cxx.h"
+#include "stream.h"
+
+namespace jsi_hermes
+{
+  namespace ffi
+  {
+    using ::facebook::hermes::HermesRuntime;
+    using ::hermes::vm::RuntimeConfig;
+
+    using c_void = void;
+
+    // RuntimeConfig::Builder and GCConfig::Builder are nested classes, which
+    // cxx can't bind to directly
+    class RuntimeConfigBuilder
+    {
+    public:
+      RuntimeConfig::Builder runtime;
+      ::hermes::vm::GCConfig::Builder gc;
+    };
+
+    std::unique_ptr<RuntimeConfigBuilder> RuntimeConfigBuilder_create() noexcept
+    {
+      return std::make_unique<RuntimeConfigBuilder>();
+    }
+
+    void RuntimeConfigBuilder_withInitHeapSize(RuntimeConfigBuilder &self, size_t bytes) noexcept
+    {
+      self.gc.withInitHeapSize(bytes);
+    }
+
+    void RuntimeConfigBuilder_withMaxHeapSize(RuntimeConfigBuilder &self, size_t bytes) noexcept
+    {
+      self.gc.withMaxHeapSize(bytes);
+    }
+
+    void RuntimeConfigBuilder_withES6Proxy(RuntimeConfigBuilder &self, bool enabled) noexcept
+    {
+      self.runtime.withES6Proxy(enabled);
+    }
+
+    void RuntimeConfigBuilder_withIntl(RuntimeConfigBuilder &self, bool enabled) noexcept
+    {
+      self.runtime.withIntl(enabled);
+    }
+
+    void RuntimeConfigBuilder_withMicrotaskQueue(RuntimeConfigBuilder &self, bool enabled) noexcept
+    {
+      self.runtime.withMicrotaskQueue(enabled);
+    }
+
+    std::unique_ptr<RuntimeConfig> RuntimeConfigBuilder_build(RuntimeConfigBuilder &self) noexcept
+    {
+      return std::make_unique<RuntimeConfig>(
+          self.runtime.withGCConfig(self.gc.build()).build());
+    }
+
+    std::unique_ptr<HermesRuntime> HermesRuntime_create(const RuntimeConfig &config)
+    {
+      return ::facebook::hermes::makeHermesRuntime(config);
+    }
+
+    ::facebook::jsi::Runtime &HermesRuntime_asRuntime(HermesRuntime &self) noexcept
+    {
+      return self;
+    }
+
+    void HermesRuntime_registerForProfiling(HermesRuntime &self)
+    {
+      self.registerForProfiling();
+    }
+
+    void HermesRuntime_unregisterForProfiling(HermesRuntime &self)
+    {
+      self.unregisterForProfiling();
+    }
+
+    // Profiler
+
+    void Profiler_enable()
+    {
+      HermesRuntime::enableSamplingProfiler();
+    }
+
+    void Profiler_disable()
+    {
+      HermesRuntime::disableSamplingProfiler();
+    }
+
+    void Profiler_dumpToFile(rust::Str path)
+    {
+      HermesRuntime::dumpSampledTraceToFile(std::string(path));
+    }
+
+    void Profiler_dumpToStream(void *closure)
+    {
+      ::jsi_rs::ffi::RustStreamBuf buf(closure);
+      std::ostream os(&buf);
+      HermesRuntime::dumpSampledTraceToStream(os);
+      os.flush();
+    }
+  }
+}
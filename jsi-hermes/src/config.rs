@@ -0,0 +1,77 @@
+use crate::ffi;
+
+/// Options used to create a [`HermesRuntime`](crate::HermesRuntime). This
+/// wraps `hermes::vm::RuntimeConfig`; use [`RuntimeConfig::builder`] to create
+/// one.
+pub struct RuntimeConfig(pub(crate) cxx::UniquePtr<ffi::RuntimeConfig>);
+
+impl RuntimeConfig {
+    pub fn builder() -> RuntimeConfigBuilder {
+        RuntimeConfigBuilder::new()
+    }
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        RuntimeConfigBuilder::new().build()
+    }
+}
+
+unsafe impl Send for RuntimeConfig {}
+
+/// Builder for [`RuntimeConfig`]. Options which are not set keep Hermes'
+/// defaults.
+pub struct RuntimeConfigBuilder(cxx::UniquePtr<ffi::RuntimeConfigBuilder>);
+
+impl RuntimeConfigBuilder {
+    pub fn new() -> Self {
+        RuntimeConfigBuilder(ffi::RuntimeConfigBuilder_create())
+    }
+
+    /// Sets the initial size of the garbage-collected heap, in bytes.
+    pub fn init_heap_size(mut self, bytes: usize) -> Self {
+        ffi::RuntimeConfigBuilder_withInitHeapSize(self.0.pin_mut(), bytes);
+        self
+    }
+
+    /// Sets the maximum size of the garbage-collected heap, in bytes. The
+    /// runtime throws an out-of-memory error if the heap grows past this.
+    pub fn max_heap_size(mut self, bytes: usize) -> Self {
+        ffi::RuntimeConfigBuilder_withMaxHeapSize(self.0.pin_mut(), bytes);
+        self
+    }
+
+    /// Enables or disables support for ES6 `Proxy` and `Reflect`.
+    pub fn es6_proxy(mut self, enabled: bool) -> Self {
+        ffi::RuntimeConfigBuilder_withES6Proxy(self.0.pin_mut(), enabled);
+        self
+    }
+
+    /// Enables or disables the `Intl` APIs. This has no effect if Hermes was
+    /// built without `Intl` support.
+    pub fn intl(mut self, enabled: bool) -> Self {
+        ffi::RuntimeConfigBuilder_withIntl(self.0.pin_mut(), enabled);
+        self
+    }
+
+    /// If enabled, promise jobs and `queueMicrotask` callbacks are put in the
+    /// JSI microtask queue, and they will not run until the host calls
+    /// [`RuntimeHandle::drain_microtasks`](jsi::RuntimeHandle::drain_microtasks).
+    /// Otherwise, Hermes uses its own job queue.
+    pub fn microtask_queue(mut self, enabled: bool) -> Self {
+        ffi::RuntimeConfigBuilder_withMicrotaskQueue(self.0.pin_mut(), enabled);
+        self
+    }
+
+    pub fn build(mut self) -> RuntimeConfig {
+        RuntimeConfig(ffi::RuntimeConfigBuilder_build(self.0.pin_mut()))
+    }
+}
+
+impl Default for RuntimeConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl Send for RuntimeConfigBuilder {}
@@ -0,0 +1,66 @@
+#[cxx::bridge]
+pub mod bridge {
+    #[namespace = "facebook::jsi"]
+    unsafe extern "C++" {
+        include!("jsi/jsi.h");
+
+        type Runtime = jsi_sys::Runtime;
+    }
+
+    #[namespace = "hermes::vm"]
+    unsafe extern "C++" {
+        include!("hermes/Public/RuntimeConfig.h");
+
+        pub type RuntimeConfig;
+    }
+
+    #[namespace = "facebook::hermes"]
+    unsafe extern "C++" {
+        include!("hermes/hermes.h");
+
+        pub type HermesRuntime;
+    }
+
+    #[namespace = "jsi_hermes::ffi"]
+    unsafe extern "C++" {
+        include!("hermes_wrapper.h");
+
+        pub type RuntimeConfigBuilder;
+
+        pub fn RuntimeConfigBuilder_create() -> UniquePtr<RuntimeConfigBuilder>;
+        pub fn RuntimeConfigBuilder_withInitHeapSize(
+            _self: Pin<&mut RuntimeConfigBuilder>,
+            bytes: usize,
+        );
+        pub fn RuntimeConfigBuilder_withMaxHeapSize(
+            _self: Pin<&mut RuntimeConfigBuilder>,
+            bytes: usize,
+        );
+        pub fn RuntimeConfigBuilder_withES6Proxy(
+            _self: Pin<&mut RuntimeConfigBuilder>,
+            enabled: bool,
+        );
+        pub fn RuntimeConfigBuilder_withIntl(_self: Pin<&mut RuntimeConfigBuilder>, enabled: bool);
+        pub fn RuntimeConfigBuilder_withMicrotaskQueue(
+            _self: Pin<&mut RuntimeConfigBuilder>,
+            enabled: bool,
+        );
+        pub fn RuntimeConfigBuilder_build(
+            _self: Pin<&mut RuntimeConfigBuilder>,
+        ) -> UniquePtr<RuntimeConfig>;
+
+        pub fn HermesRuntime_create(config: &RuntimeConfig) -> Result<UniquePtr<HermesRuntime>>;
+        pub fn HermesRuntime_asRuntime(_self: Pin<&mut HermesRuntime>) -> Pin<&mut Runtime>;
+        pub fn HermesRuntime_registerForProfiling(_self: Pin<&mut HermesRuntime>) -> Result<()>;
+        pub fn HermesRuntime_unregisterForProfiling(_self: Pin<&mut HermesRuntime>) -> Result<()>;
+
+        pub type c_void;
+
+        pub fn Profiler_enable() -> Result<()>;
+        pub fn Profiler_disable() -> Result<()>;
+        pub fn Profiler_dumpToFile(path: &str) -> Result<()>;
+        pub unsafe fn Profiler_dumpToStream(closure: *mut c_void) -> Result<()>;
+    }
+}
+
+pub use bridge::*;
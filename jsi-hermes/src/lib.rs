@@ -0,0 +1,30 @@
+//! # `jsi-hermes`
+//!
+//! Creates [Hermes](https://github.com/facebook/hermes) runtimes from Rust, so
+//! that JSI modules written with [`jsi`] can be run outside of React Native
+//! (ex. in CLI tools, on a server, or in tests).
+//!
+//! ```no_run
+//! use jsi_hermes::{HermesRuntime, RuntimeConfig};
+//!
+//! let config = RuntimeConfig::builder()
+//!     .max_heap_size(64 << 20)
+//!     .microtask_queue(true)
+//!     .build();
+//!
+//! let mut runtime = HermesRuntime::with_config(&config).unwrap();
+//! let mut rt = runtime.handle();
+//!
+//! rt.evaluate("Promise.resolve().then(() => globalThis.ready = true)", "main.js")
+//!     .unwrap();
+//! rt.drain_microtasks(None).unwrap();
+//! ```
+
+mod config;
+mod ffi;
+mod profiler;
+mod runtime;
+
+pub use config::*;
+pub use profiler::*;
+pub use runtime::*;
@@ -0,0 +1,78 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::ffi;
+
+/// Controls Hermes' sampling profiler. The profiler is shared by the whole
+/// process: while it is enabled, it periodically records the JS stack of every
+/// runtime that has been registered with
+/// [`HermesRuntime::register_for_profiling`](crate::HermesRuntime::register_for_profiling).
+///
+/// The samples are written out as a Chrome trace, which can be opened in the
+/// Performance tab of Chrome DevTools.
+///
+/// ```no_run
+/// use jsi_hermes::{HermesRuntime, Profiler};
+///
+/// let mut runtime = HermesRuntime::new();
+/// runtime.register_for_profiling().unwrap();
+///
+/// Profiler::enable().unwrap();
+/// runtime.handle().evaluate("for (let i = 0; i < 1e7; i++) {}", "busy.js").unwrap();
+/// Profiler::disable().unwrap();
+///
+/// Profiler::dump_to_file("busy.trace.json").unwrap();
+/// ```
+pub struct Profiler;
+
+impl Profiler {
+    /// Starts sampling registered runtimes.
+    pub fn enable() -> Result<(), cxx::Exception> {
+        ffi::Profiler_enable()
+    }
+
+    /// Stops sampling. Samples which have been recorded so far are kept until
+    /// they are dumped.
+    pub fn disable() -> Result<(), cxx::Exception> {
+        ffi::Profiler_disable()
+    }
+
+    /// Writes the recorded samples to the file at `path` as a Chrome trace.
+    pub fn dump_to_file(path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref().to_string_lossy();
+
+        ffi::Profiler_dumpToFile(&path).map_err(|err| io::Error::other(err.what().to_string()))
+    }
+
+    /// Writes the recorded samples to `writer` as a Chrome trace.
+    pub fn dump_to_writer<W: Write>(writer: W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+        let mut error = None;
+
+        let mut callback: jsi_sys::WriteCallback = Box::new(|bytes| {
+            // a panicking writer fails the write instead of unwinding into C++
+            let res = jsi_sys::catch_panic("writer", || writer.write_all(bytes))
+                .unwrap_or_else(|panic| Err(io::Error::other(panic)));
+
+            match res {
+                Ok(()) => true,
+                Err(err) => {
+                    error.get_or_insert(err);
+                    false
+                }
+            }
+        });
+
+        let res = unsafe {
+            ffi::Profiler_dumpToStream(&mut callback as *mut jsi_sys::WriteCallback as *mut _)
+        };
+        drop(callback);
+
+        if let Some(err) = error {
+            return Err(err);
+        }
+
+        res.map_err(|err| io::Error::other(err.what().to_string()))?;
+        writer.flush()
+    }
+}
@@ -0,0 +1,75 @@
+use jsi::RuntimeHandle;
+
+use crate::{ffi, RuntimeConfig};
+
+/// An owned Hermes runtime. The runtime is destroyed when this is dropped.
+pub struct HermesRuntime {
+    pub(crate) inner: cxx::UniquePtr<ffi::HermesRuntime>,
+    /// Whether the runtime is registered with the sampling profiler, which
+    /// has to be undone before it is destroyed.
+    registered: bool,
+}
+
+impl HermesRuntime {
+    /// Creates a runtime with the default configuration.
+    pub fn new() -> Self {
+        Self::with_config(&RuntimeConfig::default())
+            .expect("failed to create Hermes runtime with default config")
+    }
+
+    pub fn with_config(config: &RuntimeConfig) -> Result<Self, cxx::Exception> {
+        Ok(HermesRuntime {
+            inner: ffi::HermesRuntime_create(&config.0)?,
+            registered: false,
+        })
+    }
+
+    /// Returns a handle to this runtime which can be used with the rest of
+    /// `jsi`. Values created with the handle cannot outlive this borrow, so the
+    /// runtime can't be dropped while they are still in use.
+    pub fn handle(&mut self) -> RuntimeHandle<'_> {
+        let rt = ffi::HermesRuntime_asRuntime(self.inner.pin_mut());
+        RuntimeHandle::new_unchecked(unsafe { rt.get_unchecked_mut() })
+    }
+
+    /// Allows the [`Profiler`](crate::Profiler) to sample this runtime. This
+    /// must be called on the thread that the runtime is used on. Does nothing
+    /// if the runtime is already registered.
+    pub fn register_for_profiling(&mut self) -> Result<(), cxx::Exception> {
+        if !self.registered {
+            ffi::HermesRuntime_registerForProfiling(self.inner.pin_mut())?;
+            self.registered = true;
+        }
+
+        Ok(())
+    }
+
+    /// Stops the [`Profiler`](crate::Profiler) from sampling this runtime.
+    /// This happens automatically when the runtime is dropped.
+    pub fn unregister_for_profiling(&mut self) -> Result<(), cxx::Exception> {
+        if self.registered {
+            ffi::HermesRuntime_unregisterForProfiling(self.inner.pin_mut())?;
+            self.registered = false;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for HermesRuntime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for HermesRuntime {
+    fn drop(&mut self) {
+        // the profiler keeps a pointer to the runtime, so it can't be left
+        // registered after the runtime is destroyed
+        let _ = self.unregister_for_profiling();
+    }
+}
+
+// a Hermes runtime can be moved to another thread, it just can't be used from
+// more than one thread at a time
+unsafe impl Send for HermesRuntime {}
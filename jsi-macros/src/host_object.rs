@@ -332,7 +332,9 @@ impl Parse for HostObjectImpl {
 
             quote_spanned! {method_span=>
                 #js_setter_name => {
-                    self.#method_name(rt, anyhow::Context::context(jsi::FromValue::from_value(value, rt), "tried to set property to invalid value")?)?;
+                    let value = ::jsi::TryFromValue::try_from_value(value, rt)
+                        .map_err(|err: ::jsi::ConversionError| ::jsi::HostError::from(err.at_key(#js_setter_name)))?;
+                    self.#method_name(rt, value)?;
                     Ok(())
                 }
             }
@@ -358,6 +360,7 @@ impl Parse for HostObjectImpl {
                 .unzip();
 
             let arg_count = arg_names.len();
+            let arg_indices = 0..arg_count;
 
             let retval = if method.method.sig.asyncness.is_some() {
                 let trace = if cfg!(feature = "host-object-trace") {
@@ -423,9 +426,9 @@ impl Parse for HostObjectImpl {
                                             rt,
                                         ).expect("Promise.reject is not a function");
 
-                                        let err = ::jsi::js_error!(err, rt);
+                                        let err = ::jsi::IntoJsError::into_js_error(anyhow::Error::from(err), rt);
 
-                                        let rejection = promise_reject.call(::std::iter::once(err.into_value(rt)), rt).unwrap();
+                                        let rejection = promise_reject.call(::std::iter::once(err), rt).unwrap();
                                         reject.lock().unwrap().call(::std::iter::once(rejection), rt)?;
 
                                         // <Self as ::jsi::AsyncUserHostObject>::invoke(Box::new(move || {
@@ -502,10 +505,12 @@ impl Parse for HostObjectImpl {
 
                                 #(
                                     let #arg_names: #arg_types = match _args.next() {
-                                        Some(arg) => if let Some(arg) = ::jsi::FromValue::from_value(&arg, rt) {
-                                            arg
-                                        } else {
-                                            ::anyhow::bail!("argument {} could not be converted to {}", stringify!($arg_names), std::any::type_name::<#arg_types>())
+                                        Some(arg) => match ::jsi::TryFromValue::try_from_value(&arg, rt) {
+                                            Ok(arg) => arg,
+                                            Err(err) => {
+                                                let err: ::jsi::ConversionError = err;
+                                                return Err(::jsi::HostError::from(err.at_index(#arg_indices).at_key("args")).into());
+                                            }
                                         },
                                         None => {
                                             ::anyhow::bail!("not enough arguments")
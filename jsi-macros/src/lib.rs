@@ -3,6 +3,7 @@ use syn::parse_macro_input;
 
 mod host_event;
 mod host_object;
+mod value;
 
 
 /// A macro that makes it easy to define functions and properies on host
@@ -60,3 +61,55 @@ pub fn host_event_emitter(target: proc_macro::TokenStream) -> proc_macro::TokenS
     let impl_block = parse_macro_input!(target as host_event::HostEventImpl);
     proc_macro::TokenStream::from(impl_block.0)
 }
+
+/// Derives `IntoValue`, converting the type to a JS value without going
+/// through `serde`, so fields can hold JSI handles like `JsiFn` and
+/// `JsiObject`.
+///
+/// ```no_run
+/// #[derive(IntoValue, FromValue)]
+/// struct Options<'rt> {
+///     max_retries: u32,
+///     #[jsi(default)]
+///     label: Option<String>,
+///     on_progress: JsiFn<'rt>,
+/// }
+/// ```
+///
+/// - Structs with named fields become objects. Field names are converted to
+///   `camelCase` by default; set `#[jsi(rename_all = "snake_case")]` on the
+///   type to use another convention (also `PascalCase`,
+///   `SCREAMING_SNAKE_CASE` or `kebab-case`), or `#[jsi(rename = "name")]`
+///   on a field or variant to name it directly.
+/// - Structs with one unnamed field become the value of that field, those
+///   with several become arrays, and unit structs become `null`.
+/// - Enum variants are renamed like fields. By default, unit variants
+///   become strings and other variants become `{ variantName: payload }`.
+///   With `#[jsi(tag = "type")]`, variants become `{ type: "variantName",
+///   ...fields }`, and with `#[jsi(tag = "type", content = "value")]`, they
+///   become `{ type: "variantName", value: payload }`.
+/// - `#[jsi(skip)]` leaves a field out (it is set to `Default::default()`
+///   when converting from JS).
+///
+/// If the type has a lifetime parameter, the first one is used as the
+/// lifetime of the runtime.
+#[proc_macro_error]
+#[proc_macro_derive(IntoValue, attributes(jsi))]
+pub fn into_value(target: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let impl_block = parse_macro_input!(target as value::IntoValueImpl);
+    proc_macro::TokenStream::from(impl_block.0)
+}
+
+/// Derives `TryFromValue` (and therefore `FromValue`), the inverse of
+/// [`IntoValue`](derive@IntoValue), which describes the supported layouts.
+///
+/// `Option` fields accept `null` and `undefined`. Fields marked
+/// `#[jsi(default)]` are set to `Default::default()` if the property is
+/// missing. Conversion errors include the path to the property that could
+/// not be converted.
+#[proc_macro_error]
+#[proc_macro_derive(FromValue, attributes(jsi))]
+pub fn from_value(target: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let impl_block = parse_macro_input!(target as value::FromValueImpl);
+    proc_macro::TokenStream::from(impl_block.0)
+}
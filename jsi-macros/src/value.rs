@@ -0,0 +1,749 @@
+use inflector::Inflector;
+use proc_macro2::TokenStream;
+use proc_macro_error::{abort, emit_error};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse::Parse, parse_quote, Data, DeriveInput, Fields, GenericParam, Generics,
+    Ident, Lifetime, LifetimeParam, LitStr, Member,
+};
+
+extern crate proc_macro;
+
+/// `#[derive(IntoValue)]`
+pub struct IntoValueImpl(pub TokenStream);
+
+/// `#[derive(FromValue)]`, which implements `TryFromValue` (and `FromValue`
+/// through the blanket implementation)
+pub struct FromValueImpl(pub TokenStream);
+
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Camel,
+    Snake,
+    Pascal,
+    ScreamingSnake,
+    Kebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &LitStr) -> Self {
+        match lit.value().as_str() {
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "PascalCase" => Self::Pascal,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            _ => abort!(
+                lit,
+                "unknown rename rule, expected one of \"camelCase\", \"snake_case\", \"PascalCase\", \"SCREAMING_SNAKE_CASE\" or \"kebab-case\""
+            ),
+        }
+    }
+
+    fn apply(self, name: &str) -> String {
+        let name = name.strip_prefix("r#").unwrap_or(name);
+
+        match self {
+            Self::Camel => name.to_camel_case(),
+            Self::Snake => name.to_snake_case(),
+            Self::Pascal => name.to_pascal_case(),
+            Self::ScreamingSnake => name.to_screaming_snake_case(),
+            Self::Kebab => name.to_kebab_case(),
+        }
+    }
+}
+
+/// How enum variants are represented in JS.
+enum Tagging {
+    /// `"unitVariant"` or `{ variantName: payload }`
+    External,
+    /// `{ [tag]: "variantName", ...fields }`
+    Internal { tag: String },
+    /// `{ [tag]: "variantName", [content]: payload }`
+    Adjacent { tag: String, content: String },
+}
+
+struct ContainerAttrs {
+    rename_all: RenameRule,
+    tag: Option<String>,
+    content: Option<LitStr>,
+}
+
+fn container_attrs(input: &DeriveInput) -> ContainerAttrs {
+    let mut attrs = ContainerAttrs {
+        rename_all: RenameRule::Camel,
+        tag: None,
+        content: None,
+    };
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("jsi")) {
+        let res = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                attrs.rename_all = RenameRule::parse(&meta.value()?.parse()?);
+            } else if meta.path.is_ident("tag") {
+                let lit: LitStr = meta.value()?.parse()?;
+                attrs.tag = Some(lit.value());
+            } else if meta.path.is_ident("content") {
+                attrs.content = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unknown jsi attribute"));
+            }
+
+            Ok(())
+        });
+
+        if let Err(err) = res {
+            emit_error!(err.span(), "{}", err);
+        }
+    }
+
+    attrs
+}
+
+struct NameAttrs {
+    rename: Option<String>,
+    default: bool,
+    skip: bool,
+}
+
+/// Parses the attributes of a variant or field.
+fn name_attrs(attrs: &[syn::Attribute]) -> NameAttrs {
+    let mut out = NameAttrs {
+        rename: None,
+        default: false,
+        skip: false,
+    };
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("jsi")) {
+        let res = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let lit: LitStr = meta.value()?.parse()?;
+                out.rename = Some(lit.value());
+            } else if meta.path.is_ident("default") {
+                out.default = true;
+            } else if meta.path.is_ident("skip") {
+                out.skip = true;
+            } else {
+                return Err(meta.error("unknown jsi attribute"));
+            }
+
+            Ok(())
+        });
+
+        if let Err(err) = res {
+            emit_error!(err.span(), "{}", err);
+        }
+    }
+
+    out
+}
+
+struct FieldInfo {
+    member: Member,
+    /// the local variable that holds the field when the value is destructured
+    binding: Ident,
+    js_name: String,
+    default: bool,
+    skip: bool,
+}
+
+fn field_infos(fields: &Fields, rule: RenameRule) -> Vec<FieldInfo> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let attrs = name_attrs(&field.attrs);
+
+            let (member, binding, js_name) = match &field.ident {
+                Some(ident) => (
+                    Member::Named(ident.clone()),
+                    format_ident!("__field_{}", ident.unraw()),
+                    attrs
+                        .rename
+                        .unwrap_or_else(|| rule.apply(&ident.unraw().to_string())),
+                ),
+                None => {
+                    if attrs.rename.is_some() || attrs.skip || attrs.default {
+                        emit_error!(
+                            field.ty,
+                            "jsi attributes are only supported on named fields"
+                        );
+                    }
+
+                    (
+                        Member::Unnamed(idx.into()),
+                        format_ident!("__field_{}", idx),
+                        idx.to_string(),
+                    )
+                }
+            };
+
+            FieldInfo {
+                member,
+                binding,
+                js_name,
+                default: attrs.default,
+                skip: attrs.skip,
+            }
+        })
+        .collect()
+}
+
+/// Finds the lifetime that JSI handles in the type are bound to (the first
+/// lifetime parameter), or adds `'rt` if the type has no lifetimes, and
+/// requires type parameters to implement `bound`.
+fn impl_generics(generics: &Generics, bound: TokenStream) -> (Generics, Lifetime) {
+    let mut generics = generics.clone();
+
+    let lifetime = match generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => {
+            let lifetime: Lifetime = parse_quote!('rt);
+            generics.params.insert(
+                0,
+                GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+            );
+            lifetime
+        }
+    };
+
+    let type_params: Vec<_> = generics.type_params().map(|p| p.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: #bound<#lifetime>));
+    }
+
+    (generics, lifetime)
+}
+
+/// Pattern that destructures `fields` into their bindings.
+fn destructure(path: TokenStream, fields: &Fields, infos: &[FieldInfo]) -> TokenStream {
+    let members = infos.iter().map(|f| &f.member);
+    let bindings = infos.iter().map(|f| &f.binding);
+
+    match fields {
+        Fields::Named(_) | Fields::Unnamed(_) => {
+            quote! { #path { #(#members: #bindings),* } }
+        }
+        Fields::Unit => path,
+    }
+}
+
+/// Statements that set each (non-skipped) field as a property of `obj`.
+fn set_fields(infos: &[FieldInfo]) -> TokenStream {
+    let fields = infos.iter().filter(|f| !f.skip).map(|f| {
+        let binding = &f.binding;
+        let js_name = &f.js_name;
+
+        quote! {
+            let value = ::jsi::IntoValue::into_value(#binding, rt);
+            obj.set(::jsi::PropName::new(#js_name, rt), &value, rt);
+        }
+    });
+
+    quote! { #(#fields)* }
+}
+
+/// Expression that converts the bindings into a JS value: an object for named
+/// fields, the inner value for one unnamed field, an array for several
+/// unnamed fields, and `null` for no fields.
+fn fields_into_value(fields: &Fields, infos: &[FieldInfo]) -> TokenStream {
+    match fields {
+        Fields::Named(_) => {
+            let set_fields = set_fields(infos);
+
+            quote! {{
+                let mut obj = ::jsi::JsiObject::new(rt);
+                #set_fields
+                ::jsi::IntoValue::into_value(obj, rt)
+            }}
+        }
+        Fields::Unnamed(_) if infos.len() == 1 => {
+            let binding = &infos[0].binding;
+            quote! { ::jsi::IntoValue::into_value(#binding, rt) }
+        }
+        Fields::Unnamed(_) => {
+            let len = infos.len();
+            let items = infos.iter().enumerate().map(|(idx, f)| {
+                let binding = &f.binding;
+                quote! {
+                    let value = ::jsi::IntoValue::into_value(#binding, rt);
+                    arr.set(#idx, &value, rt);
+                }
+            });
+
+            quote! {{
+                let mut arr = ::jsi::JsiArray::new(#len, rt);
+                #(#items)*
+                ::jsi::IntoValue::into_value(arr, rt)
+            }}
+        }
+        Fields::Unit => quote! { ::jsi::JsiValue::new_null() },
+    }
+}
+
+/// Expression that converts `value` (a `&JsiValue`) into `T`, adding `path`
+/// to errors.
+fn convert(value: TokenStream, path: TokenStream) -> TokenStream {
+    quote! {
+        ::jsi::TryFromValue::try_from_value(#value, rt)
+            .map_err(|err: ::jsi::ConversionError| err #path)?
+    }
+}
+
+/// Expression that reads the property `key` of `obj`. If a getter throws, the
+/// exception becomes a `ConversionError` at `key` instead of a panic.
+fn get_prop(key: &str, type_name: &str, wrap: &TokenStream) -> TokenStream {
+    quote! {
+        obj.try_get(::jsi::PropName::new(#key, rt), rt).map_err(|err| {
+            ::jsi::ConversionError::new(#type_name, value, rt)
+                .with_reason(format!("getter threw an exception: {}", err))
+                .at_key(#key) #wrap
+        })?
+    }
+}
+
+/// Expression that constructs `path` from the properties of `obj`.
+fn fields_from_object(
+    path: TokenStream,
+    type_name: &str,
+    infos: &[FieldInfo],
+    wrap: &TokenStream,
+) -> TokenStream {
+    let fields = infos.iter().map(|f| {
+        let member = &f.member;
+        let js_name = &f.js_name;
+
+        if f.skip {
+            return quote! { #member: ::std::default::Default::default() };
+        }
+
+        let converted = convert(quote! { &value }, quote! { .at_key(#js_name) #wrap });
+        let get = get_prop(js_name, type_name, wrap);
+
+        if f.default {
+            quote! {
+                #member: {
+                    let value = #get;
+                    if value.is_undefined() {
+                        ::std::default::Default::default()
+                    } else {
+                        #converted
+                    }
+                }
+            }
+        } else {
+            quote! {
+                #member: {
+                    let value = #get;
+                    #converted
+                }
+            }
+        }
+    });
+
+    quote! { #path { #(#fields),* } }
+}
+
+/// Expression that converts `value` (a `&JsiValue`) into `path`, the inverse
+/// of [`fields_into_value`].
+fn fields_from_value(
+    path: TokenStream,
+    type_name: &str,
+    fields: &Fields,
+    infos: &[FieldInfo],
+    wrap: &TokenStream,
+) -> TokenStream {
+    match fields {
+        Fields::Named(_) => {
+            let construct = fields_from_object(path, type_name, infos, wrap);
+
+            quote! {{
+                let obj: ::jsi::JsiObject = ::jsi::TryFromValue::try_from_value(value, rt)
+                    .map_err(|_| ::jsi::ConversionError::new(#type_name, value, rt) #wrap)?;
+                #construct
+            }}
+        }
+        Fields::Unnamed(_) if infos.len() == 1 => {
+            let converted = convert(quote! { value }, wrap.clone());
+            quote! { #path(#converted) }
+        }
+        Fields::Unnamed(_) => {
+            let items = (0..infos.len()).map(|idx| {
+                let converted = convert(quote! { &value }, quote! { .at_index(#idx) #wrap });
+
+                quote! {{
+                    let value = arr.get(#idx, rt);
+                    #converted
+                }}
+            });
+
+            quote! {{
+                let arr: ::jsi::JsiArray = ::jsi::TryFromValue::try_from_value(value, rt)
+                    .map_err(|_| ::jsi::ConversionError::new(#type_name, value, rt) #wrap)?;
+                #path(#(#items),*)
+            }}
+        }
+        Fields::Unit => quote! {{
+            if !(value.is_null() || value.is_undefined()) {
+                return Err(::jsi::ConversionError::new(#type_name, value, rt) #wrap);
+            }
+            #path
+        }},
+    }
+}
+
+fn tagging(attrs: &ContainerAttrs) -> Tagging {
+    match (&attrs.tag, &attrs.content) {
+        (None, None) => Tagging::External,
+        (Some(tag), None) => Tagging::Internal { tag: tag.clone() },
+        (Some(tag), Some(content)) => Tagging::Adjacent {
+            tag: tag.clone(),
+            content: content.value(),
+        },
+        (None, Some(content)) => abort!(content, "`content` requires `tag`"),
+    }
+}
+
+struct VariantInfo<'a> {
+    ident: &'a Ident,
+    js_name: String,
+    fields: &'a Fields,
+    infos: Vec<FieldInfo>,
+}
+
+fn variant_infos<'a>(data: &'a syn::DataEnum, rule: RenameRule) -> Vec<VariantInfo<'a>> {
+    data.variants
+        .iter()
+        .map(|variant| {
+            let attrs = name_attrs(&variant.attrs);
+
+            if attrs.default || attrs.skip {
+                emit_error!(
+                    variant.ident,
+                    "`default` and `skip` are not supported on variants"
+                );
+            }
+
+            VariantInfo {
+                ident: &variant.ident,
+                js_name: attrs
+                    .rename
+                    .unwrap_or_else(|| rule.apply(&variant.ident.to_string())),
+                fields: &variant.fields,
+                infos: field_infos(&variant.fields, rule),
+            }
+        })
+        .collect()
+}
+
+impl Parse for IntoValueImpl {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let input: DeriveInput = input.parse()?;
+        let attrs = container_attrs(&input);
+
+        let name = &input.ident;
+        let (generics, lt) = impl_generics(&input.generics, quote!(::jsi::IntoValue));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let (_, ty_generics, _) = input.generics.split_for_impl();
+
+        let body = match &input.data {
+            Data::Struct(data) => {
+                let infos = field_infos(&data.fields, attrs.rename_all);
+                let pattern = destructure(quote!(Self), &data.fields, &infos);
+                let into_value = fields_into_value(&data.fields, &infos);
+
+                quote! {
+                    #[allow(unused_variables)]
+                    let #pattern = self;
+                    #into_value
+                }
+            }
+            Data::Enum(data) => {
+                let tagging = tagging(&attrs);
+
+                let arms = variant_infos(data, attrs.rename_all).into_iter().map(|v| {
+                    let ident = v.ident;
+                    let js_name = &v.js_name;
+                    let pattern = destructure(quote!(Self::#ident), v.fields, &v.infos);
+                    let is_unit = matches!(v.fields, Fields::Unit);
+
+                    let into_value = match &tagging {
+                        Tagging::External if is_unit => {
+                            quote! { ::jsi::JsiValue::new_string(#js_name, rt) }
+                        }
+                        Tagging::External => {
+                            let payload = fields_into_value(v.fields, &v.infos);
+
+                            quote! {{
+                                let payload = #payload;
+                                let mut obj = ::jsi::JsiObject::new(rt);
+                                obj.set(::jsi::PropName::new(#js_name, rt), &payload, rt);
+                                ::jsi::IntoValue::into_value(obj, rt)
+                            }}
+                        }
+                        Tagging::Internal { tag } => {
+                            if let Fields::Unnamed(_) = v.fields {
+                                emit_error!(
+                                    ident,
+                                    "internally tagged enums only support unit and struct variants"
+                                );
+                            }
+
+                            let set_fields = set_fields(&v.infos);
+
+                            quote! {{
+                                let mut obj = ::jsi::JsiObject::new(rt);
+                                let tag = ::jsi::JsiValue::new_string(#js_name, rt);
+                                obj.set(::jsi::PropName::new(#tag, rt), &tag, rt);
+                                #set_fields
+                                ::jsi::IntoValue::into_value(obj, rt)
+                            }}
+                        }
+                        Tagging::Adjacent { tag, content } => {
+                            let set_content = if is_unit {
+                                quote! {}
+                            } else {
+                                let payload = fields_into_value(v.fields, &v.infos);
+
+                                quote! {
+                                    let payload = #payload;
+                                    obj.set(::jsi::PropName::new(#content, rt), &payload, rt);
+                                }
+                            };
+
+                            quote! {{
+                                let mut obj = ::jsi::JsiObject::new(rt);
+                                let tag = ::jsi::JsiValue::new_string(#js_name, rt);
+                                obj.set(::jsi::PropName::new(#tag, rt), &tag, rt);
+                                #set_content
+                                ::jsi::IntoValue::into_value(obj, rt)
+                            }}
+                        }
+                    };
+
+                    quote! {
+                        #[allow(unused_variables)]
+                        #pattern => #into_value
+                    }
+                });
+
+                quote! {
+                    match self {
+                        #(#arms,)*
+                    }
+                }
+            }
+            Data::Union(data) => abort!(data.union_token, "unions are not supported"),
+        };
+
+        Ok(Self(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::jsi::IntoValue<#lt> for #name #ty_generics #where_clause {
+                fn into_value(self, rt: &mut ::jsi::RuntimeHandle<#lt>) -> ::jsi::JsiValue<#lt> {
+                    #body
+                }
+            }
+        }))
+    }
+}
+
+impl Parse for FromValueImpl {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let input: DeriveInput = input.parse()?;
+        let attrs = container_attrs(&input);
+
+        let name = &input.ident;
+        let type_name = name.to_string();
+        let (generics, lt) = impl_generics(&input.generics, quote!(::jsi::TryFromValue));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let (_, ty_generics, _) = input.generics.split_for_impl();
+
+        let body = match &input.data {
+            Data::Struct(data) => {
+                let infos = field_infos(&data.fields, attrs.rename_all);
+                let from_value =
+                    fields_from_value(quote!(Self), &type_name, &data.fields, &infos, &quote!());
+
+                quote! { Ok(#from_value) }
+            }
+            Data::Enum(data) => {
+                let variants = variant_infos(data, attrs.rename_all);
+
+                let variant_names = variants
+                    .iter()
+                    .map(|v| format!("{:?}", v.js_name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let unknown_variant = format!(
+                    "unknown variant {{:?}} of {}, expected one of {}",
+                    type_name, variant_names
+                );
+
+                match tagging(&attrs) {
+                    Tagging::External => {
+                        let no_variant = format!(
+                            "expected a variant of {}, which is a string or an object with one of the keys {}",
+                            type_name, variant_names
+                        );
+
+                        let unit_arms = variants
+                            .iter()
+                            .filter(|v| matches!(v.fields, Fields::Unit))
+                            .map(|v| {
+                                let ident = v.ident;
+                                let js_name = &v.js_name;
+                                quote! { #js_name => return Ok(Self::#ident), }
+                            });
+
+                        let payload_checks = variants
+                            .iter()
+                            .filter(|v| !matches!(v.fields, Fields::Unit))
+                            .map(|v| {
+                                let ident = v.ident;
+                                let js_name = &v.js_name;
+                                let from_value = fields_from_value(
+                                    quote!(Self::#ident),
+                                    &type_name,
+                                    v.fields,
+                                    &v.infos,
+                                    &quote!(.at_key(#js_name)),
+                                );
+
+                                let get = get_prop(js_name, &type_name, &quote!());
+
+                                quote! {
+                                    if obj.has(::jsi::PropName::new(#js_name, rt), rt) {
+                                        let value = &#get;
+                                        return Ok(#from_value);
+                                    }
+                                }
+                            });
+
+                        quote! {
+                            if value.is_string() {
+                                let variant: String = ::jsi::TryFromValue::try_from_value(value, rt)?;
+
+                                match variant.as_str() {
+                                    #(#unit_arms)*
+                                    _ => {
+                                        return Err(::jsi::ConversionError::new(#type_name, value, rt)
+                                            .with_reason(format!(#unknown_variant, variant)));
+                                    }
+                                }
+                            }
+
+                            let obj: ::jsi::JsiObject = ::jsi::TryFromValue::try_from_value(value, rt)
+                                .map_err(|_| ::jsi::ConversionError::new(#type_name, value, rt))?;
+
+                            #(#payload_checks)*
+
+                            Err(::jsi::ConversionError::new(#type_name, value, rt)
+                                .with_reason(#no_variant))
+                        }
+                    }
+                    Tagging::Internal { tag } => {
+                        let get_tag = get_prop(&tag, &type_name, &quote!());
+                        let arms = variants.iter().map(|v| {
+                            let ident = v.ident;
+                            let js_name = &v.js_name;
+
+                            if let Fields::Unnamed(_) = v.fields {
+                                emit_error!(
+                                    ident,
+                                    "internally tagged enums only support unit and struct variants"
+                                );
+                            }
+
+                            let construct = match v.fields {
+                                Fields::Unit => quote! { Self::#ident },
+                                _ => fields_from_object(
+                                    quote!(Self::#ident),
+                                    &type_name,
+                                    &v.infos,
+                                    &quote!(),
+                                ),
+                            };
+
+                            quote! { #js_name => Ok(#construct), }
+                        });
+
+                        quote! {
+                            let obj: ::jsi::JsiObject = ::jsi::TryFromValue::try_from_value(value, rt)
+                                .map_err(|_| ::jsi::ConversionError::new(#type_name, value, rt))?;
+
+                            let tag = #get_tag;
+                            let variant: String = ::jsi::TryFromValue::try_from_value(&tag, rt)
+                                .map_err(|err: ::jsi::ConversionError| err.at_key(#tag))?;
+
+                            match variant.as_str() {
+                                #(#arms)*
+                                _ => Err(::jsi::ConversionError::new(#type_name, &tag, rt)
+                                    .with_reason(format!(#unknown_variant, variant))
+                                    .at_key(#tag)),
+                            }
+                        }
+                    }
+                    Tagging::Adjacent { tag, content } => {
+                        let get_tag = get_prop(&tag, &type_name, &quote!());
+                        let get_content = get_prop(&content, &type_name, &quote!());
+                        let arms = variants.iter().map(|v| {
+                            let ident = v.ident;
+                            let js_name = &v.js_name;
+
+                            let construct = match v.fields {
+                                Fields::Unit => quote! { Self::#ident },
+                                _ => fields_from_value(
+                                    quote!(Self::#ident),
+                                    &type_name,
+                                    v.fields,
+                                    &v.infos,
+                                    &quote!(.at_key(#content)),
+                                ),
+                            };
+
+                            quote! {
+                                #js_name => {
+                                    #[allow(unused_variables)]
+                                    let value = &#get_content;
+                                    Ok(#construct)
+                                }
+                            }
+                        });
+
+                        quote! {
+                            let obj: ::jsi::JsiObject = ::jsi::TryFromValue::try_from_value(value, rt)
+                                .map_err(|_| ::jsi::ConversionError::new(#type_name, value, rt))?;
+
+                            let tag = #get_tag;
+                            let variant: String = ::jsi::TryFromValue::try_from_value(&tag, rt)
+                                .map_err(|err: ::jsi::ConversionError| err.at_key(#tag))?;
+
+                            match variant.as_str() {
+                                #(#arms)*
+                                _ => Err(::jsi::ConversionError::new(#type_name, &tag, rt)
+                                    .with_reason(format!(#unknown_variant, variant))
+                                    .at_key(#tag)),
+                            }
+                        }
+                    }
+                }
+            }
+            Data::Union(data) => abort!(data.union_token, "unions are not supported"),
+        };
+
+        Ok(Self(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::jsi::TryFromValue<#lt> for #name #ty_generics #where_clause {
+                fn try_from_value(
+                    value: &::jsi::JsiValue<#lt>,
+                    rt: &mut ::jsi::RuntimeHandle<#lt>,
+                ) -> ::std::result::Result<Self, ::jsi::ConversionError> {
+                    #body
+                }
+            }
+        }))
+    }
+}
@@ -48,7 +48,7 @@ fn main() {
         .exported_header_dirs
         .extend(includes.iter().map(|e| e.as_path()));
 
-    let mut bridges = vec!["src/ffi/base.rs", "src/ffi/host.rs"];
+    let mut bridges = vec!["src/ffi/base.rs", "src/ffi/buffer.rs", "src/ffi/host.rs"];
 
     if let Some("android") = target_os {
         bridges.push("src/ffi/android.rs");
@@ -64,5 +64,7 @@ fn main() {
         .compile("jsi");
 
     println!("cargo:rerun-if-changed=include/wrapper.h");
+    println!("cargo:rerun-if-changed=include/buffer.h");
     println!("cargo:rerun-if-changed=include/host.h");
+    println!("cargo:rerun-if-changed=include/stream.h");
 }
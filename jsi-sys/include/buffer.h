@@ -0,0 +1,48 @@
+#ifndef JSI_BUFFER_H
+#define JSI_BUFFER_H
+#pragma once
+
+#include "rust/cxx.h"
+#include "jsi/jsi.h"
+
+namespace jsi_rs
+{
+  namespace ffi
+  {
+    using Buffer = ::facebook::jsi::Buffer;
+
+    struct RustBuffer;
+
+    ::rust::Slice<const ::std::uint8_t> rust_buffer_bytes(
+        RustBuffer const &_self) noexcept;
+
+    // a jsi::Buffer whose contents are owned by Rust, so that scripts and
+    // bytecode can be passed to the runtime without being copied into a
+    // std::string first
+    class CxxBuffer : public Buffer
+    {
+    public:
+      rust::Box<RustBuffer> inner;
+
+      CxxBuffer(rust::Box<RustBuffer> it) : Buffer(), inner(std::move(it)) {}
+
+      size_t size() const override
+      {
+        return rust_buffer_bytes(*inner).size();
+      }
+
+      const uint8_t *data() const override
+      {
+        return rust_buffer_bytes(*inner).data();
+      }
+    };
+
+    ::std::shared_ptr<Buffer> CxxBuffer_create(
+        rust::Box<::jsi_rs::ffi::RustBuffer> inner) noexcept
+    {
+      return std::make_shared<CxxBuffer>(std::move(inner));
+    }
+  }
+}
+
+#endif
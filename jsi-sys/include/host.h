@@ -17,14 +17,17 @@ namespace jsi_rs
 
     struct RustHostObject;
 
+    // if the host object wants to throw a JS value, it is stored in `thrown`
     ::std::unique_ptr<Value> rho_get(
-        RustHostObject &_self, Runtime &rt, const PropNameID &name);
+        RustHostObject &_self, Runtime &rt, const PropNameID &name,
+        ::std::unique_ptr<Value> &thrown);
 
     void rho_set(
-        RustHostObject &_self, Runtime &rt, const PropNameID &name, const Value &value);
+        RustHostObject &_self, Runtime &rt, const PropNameID &name, const Value &value,
+        ::std::unique_ptr<Value> &thrown);
 
     ::std::unique_ptr<::std::vector<PropNameID>> rho_properties(
-        RustHostObject &_self, Runtime &rt) noexcept;
+        RustHostObject &_self, Runtime &rt);
 
     class CxxHostObject : public HostObject
     {
@@ -35,32 +38,58 @@ namespace jsi_rs
 
       Value get(Runtime &rt, const PropNameID &name)
       {
+        std::unique_ptr<Value> thrown;
+        std::unique_ptr<Value> value;
+
         try
         {
-          auto value = rho_get(*inner, rt, name);
-          return std::move(*value.release());
+          value = rho_get(*inner, rt, name, thrown);
         }
         catch (rust::Error &e)
         {
           throw JSError(rt, e.what());
         }
+
+        if (thrown)
+        {
+          throw JSError(rt, std::move(*thrown));
+        }
+
+        return std::move(*value.release());
       }
 
       void set(Runtime &rt, const PropNameID &name, Value const &value)
       {
+        std::unique_ptr<Value> thrown;
+
         try
         {
-          rho_set(*inner, rt, name, value);
+          rho_set(*inner, rt, name, value, thrown);
         }
         catch (rust::Error &e)
         {
           throw JSError(rt, e.what());
         }
+
+        if (thrown)
+        {
+          throw JSError(rt, std::move(*thrown));
+        }
       }
 
       std::vector<PropNameID> getPropertyNames(Runtime &rt)
       {
-        auto value = rho_properties(*inner, rt);
+        std::unique_ptr<std::vector<PropNameID>> value;
+
+        try
+        {
+          value = rho_properties(*inner, rt);
+        }
+        catch (rust::Error &e)
+        {
+          throw JSError(rt, e.what());
+        }
+
         return std::move(*value.release());
       }
     };
@@ -0,0 +1,55 @@
+#ifndef JSI_STREAM_H
+#define JSI_STREAM_H
+#pragma once
+
+#include <cstdint>
+#include <streambuf>
+#include "rust/cxx.h"
+
+namespace jsi_rs
+{
+namespace ffi
+{
+// std::ffi::c_void is not supported by CXX
+using c_void = void;
+
+// defined in Rust, in jsi-sys/src/ffi/base.rs
+bool write_trampoline(
+    ::jsi_rs::ffi::c_void *closure, ::rust::Slice<const ::std::uint8_t> bytes
+);
+
+// a stream buffer which passes everything that is written to it to a Rust
+// closure, so that large outputs don't need to be buffered in C++. the
+// closure is a pointer to a `jsi_sys::WriteCallback`, and it is borrowed for
+// as long as the stream buffer is alive
+class RustStreamBuf : public std::streambuf
+{
+public:
+  explicit RustStreamBuf(void *closure) : closure(closure) {}
+
+protected:
+  std::streamsize xsputn(const char *s, std::streamsize n) override
+  {
+    ::rust::Slice<const ::std::uint8_t> bytes(
+        (const ::std::uint8_t *)s, (size_t)n
+    );
+    return write_trampoline(closure, bytes) ? n : 0;
+  }
+
+  int_type overflow(int_type ch) override
+  {
+    if (traits_type::eq_int_type(ch, traits_type::eof())) {
+      return traits_type::not_eof(ch);
+    }
+
+    char c = traits_type::to_char_type(ch);
+    return xsputn(&c, 1) == 1 ? ch : traits_type::eof();
+  }
+
+private:
+  void *closure;
+};
+} // namespace ffi
+} // namespace jsi_rs
+
+#endif
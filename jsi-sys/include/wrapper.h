@@ -8,9 +8,13 @@
 
 #include <ReactCommon/CallInvoker.h>
 
+#include <jsi/instrumentation.h>
 #include <jsi/jsi.h>
+#include <ostream>
 #include <rust/cxx.h>
 
+#include "stream.h"
+
 namespace jsi_rs
 {
 namespace ffi
@@ -19,6 +23,7 @@ using Buffer = ::facebook::jsi::Buffer;
 using StringBuffer = ::facebook::jsi::StringBuffer;
 using PreparedJavaScript = ::facebook::jsi::PreparedJavaScript;
 using Symbol = ::facebook::jsi::Symbol;
+using BigInt = ::facebook::jsi::BigInt;
 using String = ::facebook::jsi::String;
 using Object = ::facebook::jsi::Object;
 using WeakObject = ::facebook::jsi::WeakObject;
@@ -37,9 +42,6 @@ using PropNameID = ::facebook::jsi::PropNameID;
 
 using ConstPreparedJavaScript = const PreparedJavaScript;
 
-// std::ffi::c_void is not supported by CXX
-using c_void = void;
-
 // HostObject
 
 ::std::unique_ptr<Value> HostObject_get(
@@ -64,6 +66,13 @@ using c_void = void;
   return ::std::unique_ptr<std::vector<PropNameID>>(&value);
 }
 
+// Buffer
+
+::std::shared_ptr<Buffer> Buffer_fromUtf8(rust::Str str) noexcept
+{
+  return ::std::make_shared<StringBuffer>(std::string(str));
+}
+
 // Runtime
 
 ::std::shared_ptr<ConstPreparedJavaScript>
@@ -73,22 +82,32 @@ PreparedJavaScript_asConst(const ::std::shared_ptr<PreparedJavaScript> &js
   return ::std::shared_ptr<ConstPreparedJavaScript>(js);
 }
 
+// if the script throws a JS exception, the exception is stored in `thrown`
+// and a null pointer is returned; other exceptions (such as a failure to
+// compile the script) are propagated to Rust as `cxx::Exception`s
 ::std::unique_ptr<Value> Runtime_evaluateJavaScript(
     ::facebook::jsi::Runtime &self, const std::shared_ptr<Buffer> &buffer,
-    rust::Str sourceURL
-) noexcept
+    rust::Str sourceURL, ::std::unique_ptr<Value> &thrown
+)
 {
   Value (::facebook::jsi::Runtime::*data$)(
       const std::shared_ptr<const Buffer> &buffer, const std::string &sourceURL
   ) = &::facebook::jsi::Runtime::evaluateJavaScript;
-  auto value = (self.*data$)(buffer, std::string(sourceURL));
-  return ::std::make_unique<Value>(std::move(value));
+  try {
+    auto value = (self.*data$)(buffer, std::string(sourceURL));
+    return ::std::make_unique<Value>(std::move(value));
+  } catch (JSError &e) {
+    thrown = ::std::make_unique<Value>(self, e.value());
+    return ::std::unique_ptr<Value>(nullptr);
+  }
 }
 
+// a failure to compile the script is propagated to Rust as a
+// `cxx::Exception`
 ::std::shared_ptr<ConstPreparedJavaScript> Runtime_prepareJavaScript(
     ::facebook::jsi::Runtime &self, const std::shared_ptr<Buffer> &buffer,
     rust::Str sourceURL
-) noexcept
+)
 {
   std::shared_ptr<ConstPreparedJavaScript> (::facebook::jsi::Runtime::*data$)(
       const std::shared_ptr<const Buffer> &buffer, const std::string sourceURL
@@ -96,16 +115,39 @@ PreparedJavaScript_asConst(const ::std::shared_ptr<PreparedJavaScript> &js
   return (self.*data$)(buffer, std::string(sourceURL));
 }
 
+// exceptions are handled the same way as in Runtime_evaluateJavaScript
 ::std::unique_ptr<Value> Runtime_evaluatePreparedJavaScript(
     ::facebook::jsi::Runtime &self,
-    const ::std::shared_ptr<ConstPreparedJavaScript> &js
-) noexcept
+    const ::std::shared_ptr<ConstPreparedJavaScript> &js,
+    ::std::unique_ptr<Value> &thrown
+)
 {
   Value (::facebook::jsi::Runtime::*
              data$)(const ::std::shared_ptr<ConstPreparedJavaScript> &) =
       &::facebook::jsi::Runtime::evaluatePreparedJavaScript;
-  auto value = (self.*data$)(::std::shared_ptr<ConstPreparedJavaScript>(js));
-  return ::std::make_unique<Value>(std::move(value));
+  try {
+    auto value =
+        (self.*data$)(::std::shared_ptr<ConstPreparedJavaScript>(js));
+    return ::std::make_unique<Value>(std::move(value));
+  } catch (JSError &e) {
+    thrown = ::std::make_unique<Value>(self, e.value());
+    return ::std::unique_ptr<Value>(nullptr);
+  }
+}
+
+// if a microtask throws a JS exception, the exception is stored in `thrown`
+// and the remaining microtasks are left in the queue
+bool Runtime_drainMicrotasks(
+    ::facebook::jsi::Runtime &self, int maxMicrotasksHint,
+    ::std::unique_ptr<Value> &thrown
+)
+{
+  try {
+    return self.drainMicrotasks(maxMicrotasksHint);
+  } catch (JSError &e) {
+    thrown = ::std::make_unique<Value>(self, e.value());
+    return false;
+  }
 }
 
 ::std::unique_ptr<Object> Runtime_global(::facebook::jsi::Runtime &self
@@ -125,6 +167,15 @@ Runtime_description(::facebook::jsi::Runtime &self) noexcept
   return ::std::make_unique<std::string>(std::move(value));
 }
 
+// Scope
+
+// the scope is popped when the pointer is dropped, so scopes must be dropped
+// in the reverse order that they were created in
+std::unique_ptr<Scope> Scope_create(Runtime &rt)
+{
+  return std::make_unique<Scope>(rt);
+}
+
 // PropNameID
 
 ::std::unique_ptr<PropNameID>
@@ -142,6 +193,13 @@ PropNameID_forString(Runtime &rt, const String &str) noexcept
   return std::make_unique<PropNameID>(std::move(value));
 }
 
+::std::unique_ptr<PropNameID>
+PropNameID_forSymbol(Runtime &rt, const Symbol &sym) noexcept
+{
+  auto value = PropNameID::forSymbol(rt, sym);
+  return std::make_unique<PropNameID>(std::move(value));
+}
+
 ::std::unique_ptr<std::string>
 PropNameID_toUtf8(const PropNameID &self, Runtime &rt) noexcept
 {
@@ -182,6 +240,55 @@ bool Symbol_compare(
   return Symbol::strictEquals(rt, self, other);
 }
 
+// BigInt
+
+std::unique_ptr<BigInt> BigInt_fromInt64(Runtime &rt, int64_t value)
+{
+  auto val = BigInt::fromInt64(rt, value);
+  return std::make_unique<BigInt>(std::move(val));
+}
+
+std::unique_ptr<BigInt> BigInt_fromUint64(Runtime &rt, uint64_t value)
+{
+  auto val = BigInt::fromUint64(rt, value);
+  return std::make_unique<BigInt>(std::move(val));
+}
+
+bool BigInt_isInt64(const BigInt &self, Runtime &rt)
+{
+  return self.isInt64(rt);
+}
+
+bool BigInt_isUint64(const BigInt &self, Runtime &rt)
+{
+  return self.isUint64(rt);
+}
+
+// these truncate, so check isInt64/isUint64 first
+int64_t BigInt_getInt64(const BigInt &self, Runtime &rt)
+{
+  return self.getInt64(rt);
+}
+
+uint64_t BigInt_getUint64(const BigInt &self, Runtime &rt)
+{
+  return self.getUint64(rt);
+}
+
+std::unique_ptr<String>
+BigInt_toString(const BigInt &self, Runtime &rt, int radix)
+{
+  auto val = self.toString(rt, radix);
+  return std::make_unique<String>(std::move(val));
+}
+
+bool BigInt_compare(
+    Runtime &rt, const BigInt &self, const BigInt &other
+) noexcept
+{
+  return BigInt::strictEquals(rt, self, other);
+}
+
 // String
 
 ::std::unique_ptr<String> String_fromUtf8(Runtime &rt, rust::Str str) noexcept
@@ -200,6 +307,33 @@ String_toString(const String &self, Runtime &rt) noexcept
   return std::make_unique<std::string>(std::move(value));
 }
 
+::std::unique_ptr<String>
+String_fromUtf16(Runtime &rt, ::rust::Slice<const ::std::uint16_t> units) noexcept
+{
+  auto value = String::createFromUtf16(
+      rt, (const char16_t *)units.data(), units.length()
+  );
+  return std::make_unique<String>(std::move(value));
+}
+
+::rust::Vec<::std::uint16_t>
+String_toUtf16(const String &self, Runtime &rt) noexcept
+{
+  auto value = self.utf16(rt);
+  ::rust::Vec<::std::uint16_t> units;
+  units.reserve(value.size());
+  for (auto unit : value) {
+    units.push_back((::std::uint16_t)unit);
+  }
+  return units;
+}
+
+std::unique_ptr<String> String_copy(const String &self, Runtime &rt) noexcept
+{
+  auto val = Value(rt, self).getString(rt);
+  return std::make_unique<String>(std::move(val));
+}
+
 bool String_compare(
     Runtime &rt, const String &self, const String &other
 ) noexcept
@@ -238,24 +372,37 @@ bool Object_compare(
   return Object::strictEquals(rt, self, other);
 }
 
+// getters and setters can throw, so these catch JS exceptions the same way
+// as Runtime_evaluateJavaScript
 std::unique_ptr<Value> Object_getProperty(
-    const Object &self, Runtime &rt, const PropNameID &name
-) noexcept
+    const Object &self, Runtime &rt, const PropNameID &name,
+    std::unique_ptr<Value> &thrown
+)
 {
   Value (::facebook::jsi::Object::*fp)(Runtime &, const PropNameID &) const =
       &::facebook::jsi::Object::getProperty;
-  auto value = (self.*fp)(rt, name);
-  return std::make_unique<Value>(std::move(value));
+  try {
+    auto value = (self.*fp)(rt, name);
+    return std::make_unique<Value>(std::move(value));
+  } catch (JSError &e) {
+    thrown = std::make_unique<Value>(rt, e.value());
+    return std::unique_ptr<Value>(nullptr);
+  }
 }
 
 void Object_setProperty(
-    Object &self, Runtime &rt, const PropNameID &name, Value const &value
-) noexcept
+    Object &self, Runtime &rt, const PropNameID &name, Value const &value,
+    std::unique_ptr<Value> &thrown
+)
 {
   void (::facebook::jsi::Object::*
             fp)(Runtime &, const PropNameID &, Value const &) const =
       &::facebook::jsi::Object::setProperty;
-  (self.*fp)(rt, name, value);
+  try {
+    (self.*fp)(rt, name, value);
+  } catch (JSError &e) {
+    thrown = std::make_unique<Value>(rt, e.value());
+  }
 }
 
 std::unique_ptr<Array> Object_asArray(Object const &self, Runtime &rt)
@@ -283,6 +430,11 @@ std::unique_ptr<Function> Object_asFunction(Object const &self, Runtime &rt)
   return std::make_unique<Function>(std::move(val));
 }
 
+bool Object_isHostObject(Object const &self, Runtime &rt)
+{
+  return self.isHostObject(rt);
+}
+
 std::shared_ptr<HostObject> Object_asHostObject(Object const &self, Runtime &rt)
 {
   std::shared_ptr<HostObject> (::facebook::jsi::Object::*fp)(Runtime &) const =
@@ -290,7 +442,7 @@ std::shared_ptr<HostObject> Object_asHostObject(Object const &self, Runtime &rt)
   return (self.*fp)(rt);
 }
 
-std::unique_ptr<Array> Object_getPropertyNames(Object &self, Runtime &rt)
+std::unique_ptr<Array> Object_getPropertyNames(Object const &self, Runtime &rt)
 {
   Array (::facebook::jsi::Object::*fp)(Runtime &) const =
       &::facebook::jsi::Object::getPropertyNames;
@@ -338,41 +490,63 @@ void Array_set(Array &self, Runtime &rt, size_t index, Value const &value)
 
 // Function
 
-std::unique_ptr<Value>
-Function_call(Function const &self, Runtime &rt, std::vector<Value> const &args)
+// JS exceptions are stored in `thrown` and a null pointer is returned, the
+// same as in Runtime_evaluateJavaScript
+
+std::unique_ptr<Value> Function_call(
+    Function const &self, Runtime &rt, std::vector<Value> const &args,
+    std::unique_ptr<Value> &thrown
+)
 {
   Value (::facebook::jsi::Function::*fp)(Runtime &, Value const *, size_t)
       const = &::facebook::jsi::Function::call;
-  auto value = (self.*fp)(rt, args.data(), args.size());
-  return std::make_unique<Value>(std::move(value));
+  try {
+    auto value = (self.*fp)(rt, args.data(), args.size());
+    return std::make_unique<Value>(std::move(value));
+  } catch (JSError &e) {
+    thrown = std::make_unique<Value>(rt, e.value());
+    return std::unique_ptr<Value>(nullptr);
+  }
 }
 
 std::unique_ptr<Value> Function_callWithThis(
     Function const &self, Runtime &rt, Object const &thisObj,
-    std::vector<Value> const &args
+    std::vector<Value> const &args, std::unique_ptr<Value> &thrown
 )
 {
   Value (::facebook::jsi::Function::*fp)(
       Runtime &, Object const &, Value const *, size_t
   ) const = &::facebook::jsi::Function::callWithThis;
-  auto value = (self.*fp)(rt, thisObj, args.data(), args.size());
-  return std::make_unique<Value>(std::move(value));
+  try {
+    auto value = (self.*fp)(rt, thisObj, args.data(), args.size());
+    return std::make_unique<Value>(std::move(value));
+  } catch (JSError &e) {
+    thrown = std::make_unique<Value>(rt, e.value());
+    return std::unique_ptr<Value>(nullptr);
+  }
 }
 
 std::unique_ptr<Value> Function_callAsConstructor(
-    Function const &self, Runtime &rt, std::vector<Value> const &args
+    Function const &self, Runtime &rt, std::vector<Value> const &args,
+    std::unique_ptr<Value> &thrown
 )
 {
   Value (::facebook::jsi::Function::*fp)(Runtime &, Value const *, size_t)
       const = &::facebook::jsi::Function::callAsConstructor;
-  auto value = (self.*fp)(rt, args.data(), args.size());
-  return std::make_unique<Value>(std::move(value));
+  try {
+    auto value = (self.*fp)(rt, args.data(), args.size());
+    return std::make_unique<Value>(std::move(value));
+  } catch (JSError &e) {
+    thrown = std::make_unique<Value>(rt, e.value());
+    return std::unique_ptr<Value>(nullptr);
+  }
 }
 
 ::std::unique_ptr<::facebook::jsi::Value> host_fn_trampoline(
     ::facebook::jsi::Runtime &rt, const ::facebook::jsi::Value &thisVal,
     const ::facebook::jsi::Value *args, ::std::uint32_t count,
-    ::std::size_t stride, ::jsi_rs::ffi::c_void *closure
+    ::std::size_t stride, ::jsi_rs::ffi::c_void *closure,
+    ::std::unique_ptr<::facebook::jsi::Value> &thrown
 );
 
 std::unique_ptr<Function> Function_createFromHostFunction(
@@ -385,8 +559,23 @@ std::unique_ptr<Function> Function_createFromHostFunction(
           Runtime &rt, const Value &thisVal, const Value *args, size_t count
       ) {
         auto stride = sizeof(Value);
-        auto val =
-            host_fn_trampoline(rt, thisVal, args, count, stride, closure);
+        std::unique_ptr<Value> thrown;
+        std::unique_ptr<Value> val;
+
+        try {
+          val = host_fn_trampoline(
+              rt, thisVal, args, count, stride, closure, thrown
+          );
+        } catch (rust::Error &e) {
+          throw JSError(rt, e.what());
+        }
+
+        // the host function wants to throw a JS value instead of an error
+        // created from a Rust error message
+        if (thrown) {
+          throw JSError(rt, std::move(*thrown));
+        }
+
         return std::move(*val.release());
       }
   );
@@ -435,6 +624,11 @@ std::unique_ptr<Value> Value_fromObject(Runtime &rt, std::unique_ptr<Object> o)
   return std::make_unique<Value>(rt, std::move(*o.release()));
 }
 
+std::unique_ptr<Value> Value_fromBigInt(Runtime &rt, std::unique_ptr<BigInt> b)
+{
+  return std::make_unique<Value>(rt, std::move(*b.release()));
+}
+
 std::unique_ptr<Value> Value_copyFromString(Runtime &rt, const String &s)
 {
   return std::make_unique<Value>(rt, s);
@@ -450,6 +644,11 @@ std::unique_ptr<Value> Value_copyFromObject(Runtime &rt, const Object &o)
   return std::make_unique<Value>(rt, o);
 }
 
+std::unique_ptr<Value> Value_copyFromBigInt(Runtime &rt, const BigInt &b)
+{
+  return std::make_unique<Value>(rt, b);
+}
+
 std::unique_ptr<Value> Value_fromJson(Runtime &rt, rust::Str s)
 {
   auto val =
@@ -478,6 +677,14 @@ std::unique_ptr<Symbol> Value_asSymbol(const Value &self, Runtime &rt)
   return std::make_unique<Symbol>(std::move(val));
 }
 
+std::unique_ptr<BigInt> Value_asBigInt(const Value &self, Runtime &rt)
+{
+  BigInt (::facebook::jsi::Value::*fp)(Runtime &) const & =
+      &::facebook::jsi::Value::asBigInt;
+  auto val = (self.*fp)(rt);
+  return std::make_unique<BigInt>(std::move(val));
+}
+
 std::unique_ptr<Object> Value_asObject(const Value &self, Runtime &rt)
 {
   Object (::facebook::jsi::Value::*fp)(Runtime &) const & =
@@ -500,6 +707,47 @@ std::unique_ptr<Value> Value_copy(const Value &self, Runtime &rt)
   return std::make_unique<Value>(std::move(val));
 }
 
+// Instrumentation
+
+// getHeapInfo returns an unordered_map, which cxx doesn't support, so the
+// entries are split into two vectors of the same length
+void Instrumentation_getHeapInfo(
+    Instrumentation &self, bool includeExpensive,
+    std::vector<std::string> &keys, std::vector<int64_t> &values
+)
+{
+  auto info = self.getHeapInfo(includeExpensive);
+  keys.reserve(info.size());
+  values.reserve(info.size());
+
+  for (auto &entry : info) {
+    keys.push_back(entry.first);
+    values.push_back(entry.second);
+  }
+}
+
+void Instrumentation_collectGarbage(Instrumentation &self, rust::Str cause)
+{
+  self.collectGarbage(std::string(cause));
+}
+
+void Instrumentation_createSnapshotToFile(
+    Instrumentation &self, rust::Str path
+)
+{
+  self.createSnapshotToFile(std::string(path));
+}
+
+void Instrumentation_createSnapshotToStream(
+    Instrumentation &self, void *closure
+)
+{
+  RustStreamBuf buf(closure);
+  std::ostream os(&buf);
+  self.createSnapshotToStream(os);
+  os.flush();
+}
+
 // CallInvoker
 
 void call_invoker_trampoline(void *closure);
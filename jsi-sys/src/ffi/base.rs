@@ -1,3 +1,5 @@
+use crate::panic::catch_panic;
+
 #[cxx::bridge]
 pub mod ffi {
     #[namespace = "facebook::jsi"]
@@ -12,6 +14,9 @@ pub mod ffi {
 
         pub type StringBuffer;
 
+        #[namespace = "jsi_rs::ffi"]
+        pub fn Buffer_fromUtf8(str: &str) -> SharedPtr<Buffer>;
+
         pub type PreparedJavaScript;
 
         #[namespace = "jsi_rs::ffi"]
@@ -34,28 +39,60 @@ pub mod ffi {
             _self: Pin<&mut Runtime>,
             buffer: &SharedPtr<Buffer>,
             source_url: &str,
-        ) -> UniquePtr<JsiValue>;
+            thrown: &mut UniquePtr<JsiValue>,
+        ) -> Result<UniquePtr<JsiValue>>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Runtime_prepareJavaScript(
             _self: Pin<&mut Runtime>,
             buffer: &SharedPtr<Buffer>,
             source_url: &str,
-        ) -> SharedPtr<ConstPreparedJavaScript>;
+        ) -> Result<SharedPtr<ConstPreparedJavaScript>>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Runtime_evaluatePreparedJavaScript(
             _self: Pin<&mut Runtime>,
             js: &SharedPtr<ConstPreparedJavaScript>,
-        ) -> UniquePtr<JsiValue>;
+            thrown: &mut UniquePtr<JsiValue>,
+        ) -> Result<UniquePtr<JsiValue>>;
         #[cxx_name = "drainMicrotasks"]
         pub fn drain_microtasks(self: Pin<&mut Runtime>, max_microtasks_hint: i32) -> bool;
         #[namespace = "jsi_rs::ffi"]
+        pub fn Runtime_drainMicrotasks(
+            _self: Pin<&mut Runtime>,
+            max_microtasks_hint: i32,
+            thrown: &mut UniquePtr<JsiValue>,
+        ) -> Result<bool>;
+        #[namespace = "jsi_rs::ffi"]
         pub fn Runtime_global(_self: Pin<&mut Runtime>) -> UniquePtr<JsiObject>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Runtime_description(_self: Pin<&mut Runtime>) -> UniquePtr<CxxString>;
+
+        #[namespace = "jsi_rs::ffi"]
+        pub fn Scope_create(rt: Pin<&mut Runtime>) -> UniquePtr<Scope>;
+
         #[cxx_name = "isInspectable"]
         pub fn is_inspectable(self: Pin<&mut Runtime>) -> bool;
         pub fn instrumentation(self: Pin<&mut Runtime>) -> Pin<&mut Instrumentation>;
 
+        #[namespace = "jsi_rs::ffi"]
+        pub fn Instrumentation_getHeapInfo(
+            _self: Pin<&mut Instrumentation>,
+            include_expensive: bool,
+            keys: Pin<&mut CxxVector<CxxString>>,
+            values: Pin<&mut CxxVector<i64>>,
+        );
+        #[namespace = "jsi_rs::ffi"]
+        pub fn Instrumentation_collectGarbage(_self: Pin<&mut Instrumentation>, cause: &str);
+        #[namespace = "jsi_rs::ffi"]
+        pub fn Instrumentation_createSnapshotToFile(
+            _self: Pin<&mut Instrumentation>,
+            path: &str,
+        ) -> Result<()>;
+        #[namespace = "jsi_rs::ffi"]
+        pub unsafe fn Instrumentation_createSnapshotToStream(
+            _self: Pin<&mut Instrumentation>,
+            closure: *mut c_void,
+        ) -> Result<()>;
+
         pub type HostObject;
 
         #[namespace = "jsi_rs::ffi"]
@@ -88,6 +125,11 @@ pub mod ffi {
             str: &JsiString,
         ) -> UniquePtr<PropNameID>;
         #[namespace = "jsi_rs::ffi"]
+        pub fn PropNameID_forSymbol(
+            rt: Pin<&mut Runtime>,
+            sym: &JsiSymbol,
+        ) -> UniquePtr<PropNameID>;
+        #[namespace = "jsi_rs::ffi"]
         pub fn PropNameID_toUtf8(_self: &PropNameID, rt: Pin<&mut Runtime>)
             -> UniquePtr<CxxString>;
         #[namespace = "jsi_rs::ffi"]
@@ -106,6 +148,29 @@ pub mod ffi {
         #[namespace = "jsi_rs::ffi"]
         pub fn Symbol_toString(_self: &JsiSymbol, rt: Pin<&mut Runtime>) -> UniquePtr<CxxString>;
 
+        #[cxx_name = "BigInt"]
+        pub type JsiBigInt;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn BigInt_fromInt64(rt: Pin<&mut Runtime>, value: i64) -> UniquePtr<JsiBigInt>;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn BigInt_fromUint64(rt: Pin<&mut Runtime>, value: u64) -> UniquePtr<JsiBigInt>;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn BigInt_isInt64(_self: &JsiBigInt, rt: Pin<&mut Runtime>) -> bool;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn BigInt_isUint64(_self: &JsiBigInt, rt: Pin<&mut Runtime>) -> bool;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn BigInt_getInt64(_self: &JsiBigInt, rt: Pin<&mut Runtime>) -> i64;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn BigInt_getUint64(_self: &JsiBigInt, rt: Pin<&mut Runtime>) -> u64;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn BigInt_toString(
+            _self: &JsiBigInt,
+            rt: Pin<&mut Runtime>,
+            radix: i32,
+        ) -> Result<UniquePtr<JsiString>>;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn BigInt_compare(rt: Pin<&mut Runtime>, lhs: &JsiBigInt, rhs: &JsiBigInt) -> bool;
+
         #[cxx_name = "String"]
         pub type JsiString;
         #[namespace = "jsi_rs::ffi"]
@@ -114,6 +179,12 @@ pub mod ffi {
         pub fn String_compare(rt: Pin<&mut Runtime>, lhs: &JsiString, rhs: &JsiString) -> bool;
         #[namespace = "jsi_rs::ffi"]
         pub fn String_toString(_self: &JsiString, rt: Pin<&mut Runtime>) -> UniquePtr<CxxString>;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn String_fromUtf16(rt: Pin<&mut Runtime>, units: &[u16]) -> UniquePtr<JsiString>;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn String_toUtf16(_self: &JsiString, rt: Pin<&mut Runtime>) -> Vec<u16>;
+        #[namespace = "jsi_rs::ffi"]
+        pub fn String_copy(_self: &JsiString, rt: Pin<&mut Runtime>) -> UniquePtr<JsiString>;
 
         #[cxx_name = "Object"]
         pub type JsiObject;
@@ -138,7 +209,8 @@ pub mod ffi {
             _self: &JsiObject,
             rt: Pin<&mut Runtime>,
             prop: &PropNameID,
-        ) -> UniquePtr<JsiValue>;
+            thrown: &mut UniquePtr<JsiValue>,
+        ) -> Result<UniquePtr<JsiValue>>;
         #[cxx_name = "hasProperty"]
         pub fn has_property(self: &JsiObject, rt: Pin<&mut Runtime>, prop: &PropNameID) -> bool;
         #[namespace = "jsi_rs::ffi"]
@@ -147,7 +219,8 @@ pub mod ffi {
             rt: Pin<&mut Runtime>,
             prop: &PropNameID,
             value: &JsiValue,
-        );
+            thrown: &mut UniquePtr<JsiValue>,
+        ) -> Result<()>;
         #[cxx_name = "isArray"]
         pub fn is_array(self: &JsiObject, rt: Pin<&mut Runtime>) -> bool;
         #[cxx_name = "isArrayBuffer"]
@@ -174,13 +247,15 @@ pub mod ffi {
             rt: Pin<&mut Runtime>,
         ) -> Result<UniquePtr<JsiFunction>>;
         #[namespace = "jsi_rs::ffi"]
+        pub fn Object_isHostObject(_self: &JsiObject, rt: Pin<&mut Runtime>) -> bool;
+        #[namespace = "jsi_rs::ffi"]
         pub fn Object_asHostObject(
             _self: &JsiObject,
             rt: Pin<&mut Runtime>,
         ) -> Result<SharedPtr<HostObject>>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Object_getPropertyNames(
-            _self: Pin<&mut JsiObject>,
+            _self: &JsiObject,
             rt: Pin<&mut Runtime>,
         ) -> UniquePtr<JsiArray>;
 
@@ -228,12 +303,14 @@ pub mod ffi {
             _self: &JsiFunction,
             rt: Pin<&mut Runtime>,
             args: &CxxVector<JsiValue>,
+            thrown: &mut UniquePtr<JsiValue>,
         ) -> Result<UniquePtr<JsiValue>>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Function_callAsConstructor(
             _self: &JsiFunction,
             rt: Pin<&mut Runtime>,
             args: &CxxVector<JsiValue>,
+            thrown: &mut UniquePtr<JsiValue>,
         ) -> Result<UniquePtr<JsiValue>>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Function_callWithThis(
@@ -241,6 +318,7 @@ pub mod ffi {
             rt: Pin<&mut Runtime>,
             thisObj: &JsiObject,
             args: &CxxVector<JsiValue>,
+            thrown: &mut UniquePtr<JsiValue>,
         ) -> Result<UniquePtr<JsiValue>>;
         #[namespace = "jsi_rs::ffi"]
         pub unsafe fn Function_createFromHostFunction(
@@ -280,12 +358,19 @@ pub mod ffi {
             s: UniquePtr<JsiSymbol>,
         ) -> UniquePtr<JsiValue>;
         #[namespace = "jsi_rs::ffi"]
+        pub fn Value_fromBigInt(
+            rt: Pin<&mut Runtime>,
+            b: UniquePtr<JsiBigInt>,
+        ) -> UniquePtr<JsiValue>;
+        #[namespace = "jsi_rs::ffi"]
         pub fn Value_copyFromString(rt: Pin<&mut Runtime>, s: &JsiString) -> UniquePtr<JsiValue>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Value_copyFromObject(rt: Pin<&mut Runtime>, o: &JsiObject) -> UniquePtr<JsiValue>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Value_copyFromSymbol(rt: Pin<&mut Runtime>, s: &JsiSymbol) -> UniquePtr<JsiValue>;
         #[namespace = "jsi_rs::ffi"]
+        pub fn Value_copyFromBigInt(rt: Pin<&mut Runtime>, b: &JsiBigInt) -> UniquePtr<JsiValue>;
+        #[namespace = "jsi_rs::ffi"]
         pub fn Value_fromJson(rt: Pin<&mut Runtime>, s: &str) -> UniquePtr<JsiValue>;
         #[namespace = "jsi_rs::ffi"]
         pub fn Value_compare(rt: Pin<&mut Runtime>, lhs: &JsiValue, rhs: &JsiValue) -> bool;
@@ -301,6 +386,8 @@ pub mod ffi {
         pub fn is_string(self: &JsiValue) -> bool;
         #[cxx_name = "isSymbol"]
         pub fn is_symbol(self: &JsiValue) -> bool;
+        #[cxx_name = "isBigInt"]
+        pub fn is_bigint(self: &JsiValue) -> bool;
         #[cxx_name = "isObject"]
         pub fn is_object(self: &JsiValue) -> bool;
         #[cxx_name = "getBool"]
@@ -313,6 +400,11 @@ pub mod ffi {
             rt: Pin<&mut Runtime>,
         ) -> Result<UniquePtr<JsiString>>;
         #[namespace = "jsi_rs::ffi"]
+        pub fn Value_asBigInt(
+            _self: &JsiValue,
+            rt: Pin<&mut Runtime>,
+        ) -> Result<UniquePtr<JsiBigInt>>;
+        #[namespace = "jsi_rs::ffi"]
         pub fn Value_asObject(
             _self: &JsiValue,
             rt: Pin<&mut Runtime>,
@@ -362,9 +454,12 @@ pub mod ffi {
             count: u32,
             stride: usize,
             closure: *mut c_void,
+            thrown: &mut UniquePtr<JsiValue>,
         ) -> Result<UniquePtr<JsiValue>>;
 
         unsafe fn call_invoker_trampoline(closure: *mut c_void) -> Result<()>;
+
+        unsafe fn write_trampoline(closure: *mut c_void, bytes: &[u8]) -> bool;
     }
 }
 
@@ -382,6 +477,30 @@ pub type HostFunctionCallback<'rt> = Box<
         + 'rt,
 >;
 
+/// Can be returned from a [`HostFunctionCallback`] or a
+/// [`HostObjectImpl`](crate::HostObjectImpl) to throw a JS value, instead of an
+/// `Error` created from the message of a Rust error.
+pub struct ThrownValue(pub cxx::UniquePtr<JsiValue>);
+
+impl std::fmt::Debug for ThrownValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ThrownValue")
+    }
+}
+
+impl std::fmt::Display for ThrownValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("JavaScript exception")
+    }
+}
+
+impl std::error::Error for ThrownValue {}
+
+// the value is only accessed by the host function and host object
+// trampolines, which run on the JS thread
+unsafe impl Send for ThrownValue {}
+unsafe impl Sync for ThrownValue {}
+
 unsafe fn host_fn_trampoline(
     rt: std::pin::Pin<&mut Runtime>,
     this: &JsiValue,
@@ -389,6 +508,7 @@ unsafe fn host_fn_trampoline(
     count: u32,
     stride: usize,
     closure: *mut c_void,
+    thrown: &mut cxx::UniquePtr<JsiValue>,
 ) -> anyhow::Result<cxx::UniquePtr<JsiValue>> {
     let closure = closure as *mut HostFunctionCallback;
     let mut closure = Box::from_raw(closure);
@@ -404,14 +524,29 @@ unsafe fn host_fn_trampoline(
         args_refs.push(&*ptr);
     }
 
-    let res = closure(rt, this, &args_refs[..]);
+    let res = catch_panic("host function", || closure(rt, this, &args_refs[..]));
     Box::leak(closure);
-    res
+
+    match res.unwrap_or_else(|panic| Err(panic.into())) {
+        Err(err) if err.is::<ThrownValue>() => {
+            *thrown = err.downcast::<ThrownValue>().unwrap().0;
+            Ok(cxx::UniquePtr::null())
+        }
+        res => res,
+    }
 }
 
 pub type CallInvokerCallback<'rt> = Box<dyn FnOnce() -> anyhow::Result<()> + 'rt>;
 
 unsafe fn call_invoker_trampoline(closure: *mut c_void) -> anyhow::Result<()> {
     let closure = Box::from_raw(closure as *mut CallInvokerCallback);
-    closure()
+    catch_panic("call invoker callback", closure)?
+}
+
+pub type WriteCallback<'a> = Box<dyn FnMut(&[u8]) -> bool + 'a>;
+
+unsafe fn write_trampoline(closure: *mut c_void, bytes: &[u8]) -> bool {
+    // unlike the other trampolines, the closure is borrowed and not consumed
+    let closure = &mut *(closure as *mut WriteCallback);
+    catch_panic("write callback", || closure(bytes)).unwrap_or(false)
 }
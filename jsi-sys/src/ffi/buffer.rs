@@ -0,0 +1,23 @@
+use crate::shim::{rust_buffer_bytes, RustBuffer};
+
+#[cxx::bridge]
+pub(crate) mod ffi {
+    #[namespace = "jsi_rs::ffi"]
+    unsafe extern "C++" {
+        include!("buffer.h");
+
+        #[namespace = "facebook::jsi"]
+        pub type Buffer = crate::ffi::base::Buffer;
+
+        pub fn CxxBuffer_create(inner: Box<RustBuffer>) -> SharedPtr<Buffer>;
+    }
+
+    #[namespace = "jsi_rs::ffi"]
+    extern "Rust" {
+        type RustBuffer;
+
+        fn rust_buffer_bytes(_self: &RustBuffer) -> &[u8];
+    }
+}
+
+pub use ffi::*;
@@ -41,17 +41,19 @@ pub(crate) mod ffi {
             _self: &mut RustHostObject<'a>,
             rt: Pin<&mut Runtime>,
             name: &PropNameID,
+            thrown: &mut UniquePtr<JsiValue>,
         ) -> Result<UniquePtr<JsiValue>>;
         unsafe fn rho_set<'a>(
             _self: &mut RustHostObject<'a>,
             rt: Pin<&mut Runtime>,
             name: &PropNameID,
             value: &JsiValue,
+            thrown: &mut UniquePtr<JsiValue>,
         ) -> Result<()>;
         unsafe fn rho_properties<'a>(
             _self: &mut RustHostObject<'a>,
             rt: Pin<&mut Runtime>,
-        ) -> UniquePtr<CxxVector<PropNameID>>;
+        ) -> Result<UniquePtr<CxxVector<PropNameID>>>;
     }
 }
 
@@ -1,9 +1,11 @@
 #[cfg(target_os = "android")]
 mod android;
 mod base;
+mod buffer;
 mod host;
 
 #[cfg(target_os = "android")]
 pub use android::*;
 pub use base::*;
+pub use buffer::*;
 pub use host::*;
@@ -1,5 +1,7 @@
 mod ffi;
+mod panic;
 mod shim;
 
 pub use ffi::*;
+pub use panic::*;
 pub use shim::*;
@@ -0,0 +1,76 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::RwLock;
+
+/// A panic in Rust code called from C++ (ex. a host function) which was
+/// caught before it could unwind into C++. The trampolines turn it into an
+/// error, which is thrown into JS with the panic message.
+#[derive(Debug, Clone)]
+pub struct CaughtPanic {
+    /// The message that was passed to `panic!`, or a placeholder if the panic
+    /// payload was not a string.
+    pub message: String,
+    /// What was being called when the panic happened (ex. `host function`).
+    pub origin: &'static str,
+}
+
+impl std::fmt::Display for CaughtPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Rust panic in {}: {}", self.origin, self.message)
+    }
+}
+
+impl std::error::Error for CaughtPanic {}
+
+pub type PanicHook = Box<dyn Fn(&CaughtPanic) + Send + Sync>;
+
+static PANIC_HOOK: RwLock<Option<PanicHook>> = RwLock::new(None);
+
+/// Registers a function which is called whenever a panic is caught at the FFI
+/// boundary, ex. to log it or report it to a crash reporter. This replaces
+/// the previous hook. The standard panic hook still runs when the panic
+/// happens, before this one.
+pub fn set_panic_hook<F: Fn(&CaughtPanic) + Send + Sync + 'static>(hook: F) {
+    let mut current = PANIC_HOOK.write().unwrap_or_else(|err| err.into_inner());
+    *current = Some(Box::new(hook));
+}
+
+/// Unregisters the panic hook, returning it.
+pub fn take_panic_hook() -> Option<PanicHook> {
+    let mut current = PANIC_HOOK.write().unwrap_or_else(|err| err.into_inner());
+    current.take()
+}
+
+/// Calls `f`, catching any panic so that it does not unwind into C++. Every
+/// trampoline that calls user code must go through this function (this
+/// includes trampolines in runtime crates, like `jsi-hermes`).
+///
+/// Whatever `f` was using may be left in an inconsistent state by the panic;
+/// it is up to the caller to decide whether it is safe to keep using it.
+pub fn catch_panic<T>(origin: &'static str, f: impl FnOnce() -> T) -> Result<T, CaughtPanic> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let caught = CaughtPanic {
+            message: payload_message(payload.as_ref()),
+            origin,
+        };
+
+        if let Ok(hook) = PANIC_HOOK.read() {
+            if let Some(hook) = hook.as_ref() {
+                // the hook is user code too
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| hook(&caught)));
+            }
+        }
+
+        caught
+    })
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
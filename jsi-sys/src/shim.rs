@@ -1,6 +1,7 @@
 use std::pin::Pin;
 
 use crate::ffi::*;
+use crate::panic::catch_panic;
 use cxx::*;
 
 impl HostObject {
@@ -34,8 +35,17 @@ pub(crate) fn rho_get(
     rho: &mut RustHostObject,
     rt: Pin<&mut Runtime>,
     name: &PropNameID,
+    thrown: &mut UniquePtr<JsiValue>,
 ) -> anyhow::Result<UniquePtr<JsiValue>> {
-    rho.0.get(rt, name)
+    let res = catch_panic("host object getter", || rho.0.get(rt, name));
+
+    match res.unwrap_or_else(|panic| Err(panic.into())) {
+        Err(err) if err.is::<ThrownValue>() => {
+            *thrown = err.downcast::<ThrownValue>().unwrap().0;
+            Ok(UniquePtr::null())
+        }
+        res => res,
+    }
 }
 
 #[allow(dead_code)]
@@ -44,25 +54,69 @@ pub(crate) fn rho_set(
     rt: Pin<&mut Runtime>,
     name: &PropNameID,
     value: &JsiValue,
+    thrown: &mut UniquePtr<JsiValue>,
 ) -> anyhow::Result<()> {
-    rho.0.set(rt, name, value)
+    let res = catch_panic("host object setter", || rho.0.set(rt, name, value));
+
+    match res.unwrap_or_else(|panic| Err(panic.into())) {
+        Err(err) if err.is::<ThrownValue>() => {
+            *thrown = err.downcast::<ThrownValue>().unwrap().0;
+            Ok(())
+        }
+        res => res,
+    }
 }
 
 #[allow(dead_code)]
 pub(crate) fn rho_properties(
     rho: &mut RustHostObject,
     rt: Pin<&mut Runtime>,
-) -> UniquePtr<CxxVector<PropNameID>> {
+) -> anyhow::Result<UniquePtr<CxxVector<PropNameID>>> {
+    let props = catch_panic("host object property names", || rho.0.properties(rt))?;
+
     unsafe {
-        let props = rho.0.properties(rt);
         let mut vec = create_prop_name_vector();
         for prop in props {
             push_prop_name_vector(vec.pin_mut(), prop);
         }
-        vec
+        Ok(vec)
+    }
+}
+
+/// The Rust side of a `jsi::Buffer` subclass. The slice returned by `as_ref`
+/// is captured once when the buffer is created, so that `size()` and
+/// `data()` always agree, even if a later call to `as_ref` would return
+/// something else.
+pub struct RustBuffer {
+    _owner: Box<dyn AsRef<[u8]> + Send + Sync>,
+    ptr: *const u8,
+    len: usize,
+}
+
+impl RustBuffer {
+    pub fn new<T: AsRef<[u8]> + Send + Sync + 'static>(bytes: T) -> Self {
+        let owner: Box<dyn AsRef<[u8]> + Send + Sync> = Box::new(bytes);
+        let bytes = AsRef::<[u8]>::as_ref(&*owner);
+        let (ptr, len) = (bytes.as_ptr(), bytes.len());
+
+        RustBuffer {
+            _owner: owner,
+            ptr,
+            len,
+        }
     }
 }
 
+// the slice borrows from the owner, which is boxed (so it never moves) and is
+// never accessed again after the slice is captured; the owner is Send + Sync
+unsafe impl Send for RustBuffer {}
+unsafe impl Sync for RustBuffer {}
+
+#[allow(dead_code)]
+pub(crate) fn rust_buffer_bytes(buf: &RustBuffer) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(buf.ptr, buf.len) }
+}
+
 pub trait HostObjectImpl {
     fn get(
         &mut self,
@@ -78,28 +132,40 @@ pub trait HostObjectImpl {
     fn properties(&mut self, rt: Pin<&mut Runtime>) -> Vec<UniquePtr<PropNameID>>;
 }
 
+impl Buffer {
+    pub fn from_str(s: &str) -> SharedPtr<Self> {
+        unsafe { Buffer_fromUtf8(s) }
+    }
+
+    pub fn from_rust(buf: RustBuffer) -> SharedPtr<Self> {
+        unsafe { CxxBuffer_create(Box::new(buf)) }
+    }
+}
+
 impl Runtime {
     pub fn evaluate_javascript(
         self: Pin<&mut Runtime>,
         buffer: &SharedPtr<Buffer>,
         source_url: &str,
-    ) -> UniquePtr<JsiValue> {
-        unsafe { Runtime_evaluateJavaScript(self, buffer, source_url) }
+        thrown: &mut UniquePtr<JsiValue>,
+    ) -> Result<UniquePtr<JsiValue>, cxx::Exception> {
+        unsafe { Runtime_evaluateJavaScript(self, buffer, source_url, thrown) }
     }
 
     pub fn prepare_javascript(
         self: Pin<&mut Runtime>,
         buffer: &SharedPtr<Buffer>,
         source_url: &str,
-    ) -> SharedPtr<ConstPreparedJavaScript> {
+    ) -> Result<SharedPtr<ConstPreparedJavaScript>, cxx::Exception> {
         unsafe { Runtime_prepareJavaScript(self, buffer, source_url) }
     }
 
     pub fn evaluate_prepared_javascript(
         self: Pin<&mut Runtime>,
         js: &SharedPtr<ConstPreparedJavaScript>,
-    ) -> UniquePtr<JsiValue> {
-        unsafe { Runtime_evaluatePreparedJavaScript(self, &js) }
+        thrown: &mut UniquePtr<JsiValue>,
+    ) -> Result<UniquePtr<JsiValue>, cxx::Exception> {
+        unsafe { Runtime_evaluatePreparedJavaScript(self, &js, thrown) }
     }
 
     pub fn global(self: Pin<&mut Runtime>) -> UniquePtr<JsiObject> {
@@ -166,8 +232,13 @@ impl JsiObject {
         unsafe { Object_compare(rt, self, other) }
     }
 
-    pub fn get_property(&self, rt: Pin<&mut Runtime>, prop: &PropNameID) -> UniquePtr<JsiValue> {
-        unsafe { Object_getProperty(self, rt, prop) }
+    pub fn get_property(
+        &self,
+        rt: Pin<&mut Runtime>,
+        prop: &PropNameID,
+        thrown: &mut UniquePtr<JsiValue>,
+    ) -> Result<UniquePtr<JsiValue>, cxx::Exception> {
+        unsafe { Object_getProperty(self, rt, prop, thrown) }
     }
 
     pub fn set_property(
@@ -175,8 +246,9 @@ impl JsiObject {
         rt: Pin<&mut Runtime>,
         prop: &PropNameID,
         value: &JsiValue,
-    ) {
-        unsafe { Object_setProperty(self, rt, prop, value) }
+        thrown: &mut UniquePtr<JsiValue>,
+    ) -> Result<(), cxx::Exception> {
+        unsafe { Object_setProperty(self, rt, prop, value, thrown) }
     }
 
     pub fn as_array(&self, rt: Pin<&mut Runtime>) -> Option<UniquePtr<JsiArray>> {
@@ -191,7 +263,7 @@ impl JsiObject {
         unsafe { Object_asFunction(self, rt).ok() }
     }
 
-    pub fn get_property_names(self: Pin<&mut Self>, rt: Pin<&mut Runtime>) -> UniquePtr<JsiArray> {
+    pub fn get_property_names(&self, rt: Pin<&mut Runtime>) -> UniquePtr<JsiArray> {
         unsafe { Object_getPropertyNames(self, rt) }
     }
 }
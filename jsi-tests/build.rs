@@ -1,23 +1,11 @@
-use std::{env, path::PathBuf, process::Command};
+use std::{env, path::PathBuf};
 
 fn main() {
     let pkg_base = env::var_os("CARGO_MANIFEST_DIR").unwrap();
     let pkg_base = PathBuf::from(pkg_base);
 
-    let hermes_build_status = Command::new("bash")
-        .args([pkg_base.join("../vendor/build-hermes.sh")])
-        .current_dir(pkg_base.join("../vendor"))
-        .output()
-        .expect("hermes build script could not be executed");
-
-    if !hermes_build_status.status.success() {
-        panic!(
-            "hermes build script failed\n\nstdout: {}\n\nstderr: {}",
-            String::from_utf8_lossy(&hermes_build_status.stdout),
-            String::from_utf8_lossy(&hermes_build_status.stderr),
-        )
-    }
-
+    // Hermes itself is built and linked by `jsi-hermes`, this crate only needs
+    // its headers
     let rn_base = pkg_base.join("../vendor/react-native/packages/react-native");
 
     let includes = vec![
@@ -58,18 +46,4 @@ fn main() {
         .flag_if_supported("-std=c++17")
         .files(compiles)
         .compile("js-tests");
-
-    println!("cargo:rustc-link-lib=hermes");
-    println!(
-        "cargo:rustc-link-search={}",
-        pkg_base
-            .join("../vendor/hermes/build/API/hermes/")
-            .to_string_lossy()
-    );
-    println!(
-        "cargo:rustc-env=LD_LIBRARY_PATH={}",
-        pkg_base
-            .join("../vendor/hermes/build/API/hermes/")
-            .to_string_lossy()
-    );
 }
@@ -0,0 +1,143 @@
+use jsi::{
+    BigIntValue, DeserializeValue, IntoValue, JsiBigInt, JsiFn, JsiSerializer, JsiValue,
+    RuntimeHandle, SerializeValue,
+};
+use serde::Serialize;
+
+mod common;
+
+#[test]
+fn bigint_round_trip() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let check = rt
+        .evaluate(
+            "(function (a, b, c) {
+                return typeof a === 'bigint'
+                    && a === 9007199254740993n
+                    && b === 18446744073709551615n
+                    && c === -170141183460469231731687303715884105728n
+            })",
+            "test.js",
+        )
+        .unwrap();
+    let check: JsiFn = check.try_into_js(&mut rt).unwrap();
+
+    // 2^53 + 1 can't be represented by a number
+    let args = vec![
+        BigIntValue(9_007_199_254_740_993_i64).into_value(&mut rt),
+        BigIntValue(u64::MAX).into_value(&mut rt),
+        JsiBigInt::from_i128(i128::MIN, &mut rt)
+            .unwrap()
+            .into_value(&mut rt),
+    ];
+    let out = check.call(args, &mut rt).unwrap();
+    assert!(out.try_into_js::<bool>(&mut rt).unwrap());
+
+    let big = rt.evaluate("2n ** 64n + 1n", "test.js").unwrap();
+    assert!(big.is_bigint());
+    assert_eq!(None, big.try_into_js::<u64>(&mut rt));
+    assert_eq!(Some(18446744073709551617), big.try_into_js::<i128>(&mut rt));
+
+    let big: JsiBigInt = big.try_into_js(&mut rt).unwrap();
+    assert_eq!(
+        Some("10000000000000001".to_string()),
+        big.to_string_radix(16, &mut rt)
+    );
+    assert_eq!(None, big.to_string_radix(37, &mut rt));
+
+    let id = rt.evaluate("9007199254740993n", "test.js").unwrap();
+    assert_eq!(
+        9_007_199_254_740_993,
+        u64::deserialize_value(id, &mut rt).unwrap()
+    );
+}
+
+#[test]
+fn large_integers_are_numbers() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let max = 9_007_199_254_740_991_i64.into_value(&mut rt);
+    assert!(max.is_number());
+    assert_eq!(Some(9_007_199_254_740_991), max.try_into_js::<u64>(&mut rt));
+    let min = (-9_007_199_254_740_991_i128).into_value(&mut rt);
+    assert!(min.is_number());
+
+    // numbers are not accepted where a BigInt is expected
+    assert_eq!(None, max.try_into_js::<BigIntValue<i64>>(&mut rt));
+    let big = rt.evaluate("2n ** 63n", "test.js").unwrap();
+    assert_eq!(None, big.try_into_js::<BigIntValue<i64>>(&mut rt));
+    assert_eq!(
+        Some(BigIntValue(1 << 63)),
+        big.try_into_js::<BigIntValue<u64>>(&mut rt)
+    );
+}
+
+#[test]
+fn unsafe_integers_are_rounded() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let value = (1_u64 << 53).into_value(&mut rt);
+    assert_eq!(
+        Some(9_007_199_254_740_992.),
+        value.try_into_js::<f64>(&mut rt)
+    );
+    let value = usize::MAX.into_value(&mut rt);
+    assert_eq!(Some(usize::MAX as f64), value.try_into_js::<f64>(&mut rt));
+
+    assert!(JsiValue::new_safe_integer(9_007_199_254_740_991_u64).is_ok());
+    let err = JsiValue::new_safe_integer(1_u64 << 53).err().unwrap();
+    assert_eq!(
+        "9007199254740992 is outside the safe integer range",
+        err.to_string()
+    );
+}
+
+#[test]
+fn unsafe_integer_serialization() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    // rounded by default, like before `with_safe_integers` existed
+    let value = u64::MAX.serialize_value(&mut rt).unwrap();
+    assert_eq!(Some(u64::MAX as f64), value.try_into_js::<f64>(&mut rt));
+
+    let err = u64::MAX
+        .serialize(JsiSerializer::new(&mut rt).with_safe_integers(true))
+        .unwrap_err();
+    assert_eq!(
+        "18446744073709551615 is outside the safe integer range",
+        err.to_string()
+    );
+
+    let value = u64::MAX
+        .serialize(
+            JsiSerializer::new(&mut rt)
+                .with_safe_integers(true)
+                .with_bigints(true),
+        )
+        .unwrap();
+    assert!(value.is_bigint());
+}
+
+#[test]
+fn bigint_without_global_bigint() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    rt.evaluate(
+        "globalThis.BigInt = () => { throw new Error('no') }",
+        "test.js",
+    )
+    .unwrap();
+    assert!(JsiBigInt::from_u128(u128::MAX, &mut rt).is_none());
+    assert!(JsiBigInt::from_i128(-1, &mut rt).is_some());
+
+    let err = u128::MAX
+        .serialize(JsiSerializer::new(&mut rt).with_bigints(true))
+        .unwrap_err();
+    assert!(err.to_string().contains("could not create a BigInt"));
+}
@@ -0,0 +1,151 @@
+use jsi::{host_object, IntoValue, JsiValue, JsiValueClass, PropName, RuntimeHandle};
+
+mod common;
+
+struct Counter;
+
+#[host_object]
+impl Counter {
+    pub fn count(&self, _rt: &mut RuntimeHandle) -> anyhow::Result<i32> {
+        Ok(1)
+    }
+}
+
+fn class_name(class: &JsiValueClass) -> &'static str {
+    match class {
+        JsiValueClass::Undefined => "undefined",
+        JsiValueClass::Null => "null",
+        JsiValueClass::Bool(_) => "bool",
+        JsiValueClass::Number(_) => "number",
+        JsiValueClass::String(_) => "string",
+        JsiValueClass::Symbol(_) => "symbol",
+        JsiValueClass::BigInt(_) => "bigint",
+        JsiValueClass::Array(_) => "array",
+        JsiValueClass::ArrayBuffer(_) => "arraybuffer",
+        JsiValueClass::TypedArray(_) => "typedarray",
+        JsiValueClass::Function(_) => "function",
+        JsiValueClass::UserHostObject(_) => "userhostobject",
+        JsiValueClass::HostObject(_) => "hostobject",
+        JsiValueClass::Promise(_) => "promise",
+        JsiValueClass::Date(_) => "date",
+        JsiValueClass::Map(_) => "map",
+        JsiValueClass::Set(_) => "set",
+        JsiValueClass::Error(_) => "error",
+        JsiValueClass::Object(_) => "object",
+    }
+}
+
+#[test]
+fn classify_values() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let values = rt
+        .evaluate(
+            "[
+                undefined, null, true, 1, 'a', Symbol(), 1n, [1], new ArrayBuffer(4),
+                new Uint8Array(4), new DataView(new ArrayBuffer(4)), () => {},
+                Promise.resolve(), new Date(), new Map(), new Set(), new TypeError('x'),
+                {}, Object.create(null),
+            ]",
+            "test.js",
+        )
+        .unwrap();
+    let values: Vec<JsiValue> = values.convert(&mut rt).unwrap();
+
+    let classes: Vec<_> = values
+        .iter()
+        .map(|value| class_name(&value.classify(&mut rt)))
+        .collect();
+    assert_eq!(
+        vec![
+            "undefined",
+            "null",
+            "bool",
+            "number",
+            "string",
+            "symbol",
+            "bigint",
+            "array",
+            "arraybuffer",
+            "typedarray",
+            "object",
+            "function",
+            "promise",
+            "date",
+            "map",
+            "set",
+            "error",
+            "object",
+            "object",
+        ],
+        classes
+    );
+
+    let counter = Counter.into_value(&mut rt);
+    match counter.classify(&mut rt) {
+        JsiValueClass::UserHostObject(host) => assert!(host.get_inner::<Counter>().is_some()),
+        other => panic!("expected a Rust host object, got {}", class_name(&other)),
+    }
+}
+
+#[test]
+fn classify_brand_checks() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let values = rt
+        .evaluate(
+            "[
+                Object.setPrototypeOf(new Map(), null),
+                Object.setPrototypeOf(new Set(), Object.prototype),
+                Object.setPrototypeOf(new Date(), null),
+                Object.create(Map.prototype),
+                Object.create(Set.prototype),
+                Object.create(Date.prototype),
+            ]",
+            "test.js",
+        )
+        .unwrap();
+    let values: Vec<JsiValue> = values.convert(&mut rt).unwrap();
+
+    let classes: Vec<_> = values
+        .iter()
+        .map(|value| class_name(&value.classify(&mut rt)))
+        .collect();
+    assert_eq!(
+        vec!["map", "set", "date", "object", "object", "object"],
+        classes
+    );
+}
+
+#[test]
+fn classify_with_tampered_builtins() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let values = rt
+        .evaluate("[new Date(), new Map(), {}, Promise.resolve()]", "test.js")
+        .unwrap();
+    let values: Vec<JsiValue> = values.convert(&mut rt).unwrap();
+
+    // globals which throw when they are read are skipped
+    rt.evaluate(
+        "for (const name of ['Date', 'Promise', 'ArrayBuffer']) {
+            Object.defineProperty(globalThis, name, { get() { throw new Error(name) } });
+        }",
+        "test.js",
+    )
+    .unwrap();
+    let classes: Vec<_> = values
+        .iter()
+        .map(|value| class_name(&value.classify(&mut rt)))
+        .collect();
+    assert_eq!(vec!["object", "map", "object", "object"], classes);
+
+    // detection relies on the built-in methods, so replacing them changes
+    // the result
+    rt.evaluate("Map.prototype.has = () => true", "test.js")
+        .unwrap();
+    assert_eq!("map", class_name(&values[2].classify(&mut rt)));
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use jsi::{host_object, DeserializeValue, IntoValue, JsiFn, PathSegment, PropName, RuntimeHandle};
+
+mod common;
+
+#[test]
+fn conversion_error_has_path() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let value = rt.evaluate("[[1], [2, 'x']]", "test.js").unwrap();
+    let err = value.convert::<Vec<Vec<f64>>>(&mut rt).unwrap_err();
+    assert_eq!(&[PathSegment::Index(1), PathSegment::Index(1)], err.path());
+    assert_eq!("number", err.expected());
+    assert_eq!("string", err.received());
+    assert_eq!("[1][1]: expected number, got string", err.to_string());
+
+    let value = rt.evaluate("(() => {})", "test.js").unwrap();
+    let err = value.convert::<String>(&mut rt).unwrap_err();
+    assert_eq!("expected string, got function", err.to_string());
+
+    let value = rt
+        .evaluate("({ items: [{ price: 1 }, { price: 'free' }] })", "test.js")
+        .unwrap();
+    let err = HashMap::<String, Vec<HashMap<String, u32>>>::deserialize_value(value, &mut rt)
+        .unwrap_err();
+    assert_eq!("items[1].price: expected u32, got string", err.to_string());
+    assert_eq!(
+        &[
+            PathSegment::Key("items".to_string()),
+            PathSegment::Index(1),
+            PathSegment::Key("price".to_string()),
+        ],
+        err.path()
+    );
+}
+
+struct Cart;
+
+#[host_object]
+impl Cart {
+    pub fn total(&self, _rt: &mut RuntimeHandle, prices: Vec<f64>) -> anyhow::Result<f64> {
+        Ok(prices.into_iter().sum())
+    }
+}
+
+#[test]
+fn host_object_argument_error_has_path() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let cart = Cart.into_value(&mut rt);
+    rt.global()
+        .set(PropName::new("cart", &mut rt), &cart, &mut rt);
+
+    let check = rt
+        .evaluate(
+            "(function () {
+                if (cart.total([1, 2]) !== 3) return 'wrong total';
+                try {
+                    cart.total([1, null]);
+                    return 'did not throw';
+                } catch (e) {
+                    return e instanceof TypeError
+                        && e.path === 'args[0][1]'
+                        && e.expected === 'number'
+                        && e.received === 'null'
+                        || e.message;
+                }
+            })",
+            "test.js",
+        )
+        .unwrap();
+    let check: JsiFn = check.try_into_js(&mut rt).unwrap();
+
+    let out = check.call(std::iter::empty(), &mut rt).unwrap();
+    assert_eq!(Some(true), out.try_into_js::<bool>(&mut rt));
+}
+
+#[test]
+fn new_null_is_null() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let null = jsi::JsiValue::new_null();
+    assert!(null.is_null());
+    assert!(!null.is_undefined());
+
+    let check = rt
+        .evaluate(
+            "(function (a, b) { return a === null && b === null })",
+            "test.js",
+        )
+        .unwrap();
+    let check: JsiFn = check.try_into_js(&mut rt).unwrap();
+    let none = None::<f64>.into_value(&mut rt);
+    let out = check.call([null, none], &mut rt).unwrap();
+    assert!(out.try_into_js::<bool>(&mut rt).unwrap());
+}
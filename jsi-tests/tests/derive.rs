@@ -0,0 +1,155 @@
+use jsi::{FromValue, IntoValue, JsiFn, JsiValue, PropName, RuntimeHandle};
+
+mod common;
+
+#[derive(IntoValue, FromValue)]
+struct Task<'rt> {
+    task_id: u32,
+    #[jsi(rename = "title")]
+    name: String,
+    #[jsi(default)]
+    tags: Vec<String>,
+    note: Option<String>,
+    on_done: JsiFn<'rt>,
+}
+
+#[derive(IntoValue, FromValue, Debug, PartialEq)]
+enum Shape {
+    Empty,
+    Circle { radius: f64 },
+    Square(f64),
+}
+
+#[derive(IntoValue, FromValue, Debug, PartialEq)]
+#[jsi(tag = "kind")]
+enum Event {
+    Started,
+    Progress { done: u32, total: u32 },
+}
+
+#[derive(IntoValue, FromValue, Debug, PartialEq)]
+#[jsi(tag = "type", content = "value", rename_all = "snake_case")]
+enum Setting {
+    DarkMode(bool),
+    FontSize(u8),
+}
+
+fn eval<'rt>(src: &str, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+    rt.evaluate(src, "test.js").unwrap()
+}
+
+#[test]
+fn derive_struct_round_trip() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let value = eval(
+        "({ taskId: 7, title: 'write tests', note: null, onDone: (x) => x * 2 })",
+        &mut rt,
+    );
+    let task: Task = value.convert(&mut rt).unwrap();
+    assert_eq!(7, task.task_id);
+    assert_eq!("write tests", task.name);
+    assert!(task.tags.is_empty());
+    assert_eq!(None, task.note);
+
+    let out = task
+        .on_done
+        .call([JsiValue::new_number(21.)], &mut rt)
+        .unwrap();
+    assert_eq!(Some(42.), out.try_into_js::<f64>(&mut rt));
+
+    let check: JsiFn = eval(
+        "(function (task) {
+            return task.taskId === 7
+                && task.title === 'write tests'
+                && task.tags.length === 0
+                && task.note === null
+                && task.onDone(1) === 2
+        })",
+        &mut rt,
+    )
+    .try_into_js(&mut rt)
+    .unwrap();
+    let task = task.into_value(&mut rt);
+    let out = check.call([task], &mut rt).unwrap();
+    assert_eq!(Some(true), out.try_into_js::<bool>(&mut rt));
+
+    let value = eval(
+        "({ taskId: 7, title: 'x', tags: ['a', 2], onDone() {} })",
+        &mut rt,
+    );
+    let err = value.convert::<Task>(&mut rt).err().unwrap();
+    assert_eq!("tags[1]: expected string, got number", err.to_string());
+}
+
+#[test]
+fn derive_enum_layouts() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let cases = [
+        ("'empty'", Shape::Empty),
+        ("({ circle: { radius: 2 } })", Shape::Circle { radius: 2. }),
+        ("({ square: 3 })", Shape::Square(3.)),
+    ];
+    for (src, expected) in cases {
+        let value = eval(src, &mut rt);
+        let shape: Shape = value.convert(&mut rt).unwrap();
+        assert_eq!(expected, shape);
+
+        // converting back and forth gives the same value
+        let value = shape.into_value(&mut rt);
+        assert_eq!(Some(expected), value.try_into_js::<Shape>(&mut rt));
+    }
+
+    let value = eval("({ kind: 'progress', done: 1, total: 4 })", &mut rt);
+    assert_eq!(
+        Some(Event::Progress { done: 1, total: 4 }),
+        value.try_into_js(&mut rt)
+    );
+    let value = eval("({ kind: 'started' })", &mut rt);
+    assert_eq!(Some(Event::Started), value.try_into_js(&mut rt));
+
+    let value = eval("({ type: 'font_size', value: 14 })", &mut rt);
+    assert_eq!(Some(Setting::FontSize(14)), value.try_into_js(&mut rt));
+
+    let value = Setting::DarkMode(true).into_value(&mut rt);
+    let obj: jsi::JsiObject = value.try_into_js(&mut rt).unwrap();
+    let tag = obj.get(PropName::new("type", &mut rt), &mut rt);
+    assert_eq!(Some("dark_mode".to_string()), tag.try_into_js(&mut rt));
+
+    let value = eval("({ type: 'font_size', value: 1000 })", &mut rt);
+    let err = value.convert::<Setting>(&mut rt).unwrap_err();
+    assert_eq!("value: 1000 is out of range for u8", err.to_string());
+
+    let value = eval("({ kind: 'stopped' })", &mut rt);
+    let err = value.convert::<Event>(&mut rt).unwrap_err();
+    assert_eq!(
+        "kind: unknown variant \"stopped\" of Event, expected one of \"started\", \"progress\"",
+        err.to_string()
+    );
+}
+
+#[test]
+fn derive_getter_throws() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let value = eval("({ get kind() { throw new Error('no kind') } })", &mut rt);
+    let err = value.convert::<Event>(&mut rt).unwrap_err();
+    assert_eq!(
+        "kind: getter threw an exception: Error: no kind",
+        err.to_string()
+    );
+
+    let value = eval(
+        "({ circle: { get radius() { throw new RangeError('too big') } } })",
+        &mut rt,
+    );
+    let err = value.convert::<Shape>(&mut rt).unwrap_err();
+    assert_eq!(
+        "circle.radius: getter threw an exception: RangeError: too big",
+        err.to_string()
+    );
+}
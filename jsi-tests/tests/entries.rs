@@ -0,0 +1,158 @@
+use jsi::{JsiObject, KeyFilter, RuntimeHandle};
+
+mod common;
+
+fn names<'rt>(obj: &JsiObject<'rt>, filter: KeyFilter, rt: &mut RuntimeHandle<'rt>) -> Vec<String> {
+    let keys: Vec<_> = obj.keys_with(filter, rt).unwrap().collect();
+    keys.iter().map(|key| rt.to_string(key)).collect()
+}
+
+#[test]
+fn object_entries() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    let obj: JsiObject = rt
+        .evaluate(
+            "(() => {
+                const proto = { inherited: 1, shadowed: 2 };
+                const obj = Object.create(proto);
+                obj.b = 'b';
+                obj.a = 'a';
+                obj[2] = 'two';
+                obj.shadowed = 3;
+                obj[Symbol('sym')] = 'sym';
+                Object.defineProperty(obj, 'hidden', { value: 'hidden' });
+                return obj;
+            })()",
+            "test.js",
+        )
+        .unwrap()
+        .try_into_js(rt)
+        .unwrap();
+
+    let entries: Vec<_> = obj.entries(rt).unwrap().collect();
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|(name, value)| (rt.to_string(name), rt.to_string(value)))
+        .collect();
+    assert_eq!(
+        vec![
+            ("2".to_string(), "two".to_string()),
+            ("b".to_string(), "b".to_string()),
+            ("a".to_string(), "a".to_string()),
+            ("shadowed".to_string(), "3".to_string()),
+        ],
+        entries
+    );
+
+    let values: Vec<f64> = obj
+        .values_with(
+            KeyFilter {
+                inherited: true,
+                ..Default::default()
+            },
+            rt,
+        )
+        .unwrap()
+        .filter_map(|value| value.try_into_js(rt))
+        .collect();
+    assert_eq!(vec![3., 1.], values);
+
+    assert_eq!(
+        vec!["2", "b", "a", "shadowed", "inherited"],
+        names(
+            &obj,
+            KeyFilter {
+                inherited: true,
+                ..Default::default()
+            },
+            rt
+        )
+    );
+    assert_eq!(
+        vec!["2", "b", "a", "shadowed", "hidden"],
+        names(
+            &obj,
+            KeyFilter {
+                non_enumerable: true,
+                ..Default::default()
+            },
+            rt
+        )
+    );
+
+    let all = names(
+        &obj,
+        KeyFilter {
+            inherited: true,
+            non_enumerable: true,
+            symbols: true,
+        },
+        rt,
+    );
+    assert_eq!(6, all.iter().position(|name| name == "inherited").unwrap());
+    assert!(all.contains(&"constructor".to_string()));
+    assert_eq!(1, all.iter().filter(|name| *name == "shadowed").count());
+
+    // the same as a for..in loop
+    let for_in: Vec<String> = obj
+        .properties(rt)
+        .iter(rt)
+        .collect::<Vec<_>>()
+        .iter()
+        .map(|name| rt.to_string(name))
+        .collect();
+    assert_eq!(
+        names(
+            &obj,
+            KeyFilter {
+                inherited: true,
+                ..Default::default()
+            },
+            rt
+        ),
+        for_in
+    );
+}
+
+#[test]
+fn inherited_keys_are_deduplicated() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    let obj: JsiObject = rt
+        .evaluate(
+            "(() => {
+                const sym = Symbol.for('sym');
+                const proto = { [sym]: 1, '\\ud800': 2, '\\udc00': 3, hidden: 4 };
+                const obj = Object.create(proto);
+                obj[sym] = 5;
+                obj['\\ud800'] = 6;
+                Object.defineProperty(obj, 'hidden', { value: 7 });
+                return obj;
+            })()",
+            "test.js",
+        )
+        .unwrap()
+        .try_into_js(rt)
+        .unwrap();
+
+    let values: Vec<f64> = obj
+        .values_with(
+            KeyFilter {
+                inherited: true,
+                symbols: true,
+                ..Default::default()
+            },
+            rt,
+        )
+        .unwrap()
+        .filter_map(|value| value.try_into_js(rt))
+        .collect();
+    // the non-enumerable own `hidden` still shadows the inherited one, and
+    // the two lone surrogates are different names
+    assert_eq!(vec![6., 5., 3.], values);
+}
@@ -0,0 +1,241 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::Context;
+use jsi::{
+    EvaluateError, HostError, IntoJsError, IntoValue, JsiFn, JsiObject, JsiString, PropName,
+    RuntimeHandle,
+};
+use jsi_hermes::HermesRuntime;
+
+mod common;
+
+fn eval_fn<'rt>(src: &str, rt: &mut RuntimeHandle<'rt>) -> JsiFn<'rt> {
+    let f = rt.evaluate(src, "test.js").unwrap();
+    f.try_into_js(rt).unwrap()
+}
+
+#[test]
+fn call_throws_error() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let f = eval_fn(
+        "(function fail() {
+            throw new RangeError('too big', { cause: new Error('inner') });
+        })",
+        &mut rt,
+    );
+
+    let err = f.call(std::iter::empty(), &mut rt).unwrap_err();
+    assert_eq!("RangeError", err.name());
+    assert_eq!("too big", err.message());
+    assert_eq!("RangeError: too big", err.to_string());
+    assert!(err.stack().unwrap().contains("fail"));
+    assert!(!err.is_native());
+
+    let cause = err.cause().unwrap();
+    assert_eq!("Error", cause.name());
+    assert_eq!("inner", cause.message());
+}
+
+#[test]
+fn getter_throws_error() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let obj = rt
+        .evaluate("({ get broken() { throw 'nope' } })", "test.js")
+        .unwrap();
+    let obj: JsiObject = obj.try_into_js(&mut rt).unwrap();
+
+    let err = obj
+        .try_get(PropName::new("broken", &mut rt), &mut rt)
+        .unwrap_err();
+    assert_eq!("", err.name());
+    assert_eq!("nope", err.message());
+}
+
+#[test]
+fn host_fn_rethrows_js_error() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let fail = eval_fn(
+        "(function () { const e = new TypeError('original'); e.code = 42; throw e })",
+        &mut rt,
+    );
+
+    let host = JsiFn::from_host_fn(
+        &PropName::new("host", &mut rt),
+        0,
+        Box::new(move |_this, _args, rt| {
+            let value = fail.call(std::iter::empty(), rt)?;
+            Ok(value)
+        }),
+        &mut rt,
+    );
+
+    let check = eval_fn(
+        "(function (host) {
+            try { host() } catch (e) { return e instanceof TypeError && e.code === 42 }
+            return false
+        })",
+        &mut rt,
+    );
+
+    let host = JsiObject::from(host).into_value(&mut rt);
+    let out = check.call(std::iter::once(host), &mut rt).unwrap();
+    assert!(out.try_into_js::<bool>(&mut rt).unwrap());
+}
+
+#[test]
+fn detached_error_outlives_runtime() {
+    let detached = {
+        let mut runtime = HermesRuntime::new();
+        let mut rt = runtime.handle();
+
+        match rt.evaluate(
+            "throw new TypeError('gone', { cause: new Error('inner') })",
+            "test.js",
+        ) {
+            Err(EvaluateError::Thrown(err)) => err.detach(),
+            _ => panic!("expected an exception"),
+        }
+    };
+
+    assert_eq!("TypeError: gone", detached.to_string());
+    assert_eq!("inner", detached.cause().unwrap().message());
+    assert!(!detached.is_native());
+
+    // the original value is gone, so a new error is thrown in its place
+    let mut runtime = HermesRuntime::new();
+    let mut rt = runtime.handle();
+    assert!(detached.value(&mut rt).is_none());
+
+    let check = eval_fn(
+        "(function (e) {
+            return e instanceof TypeError && e.message === 'gone' && e.cause.message === 'inner'
+        })",
+        &mut rt,
+    );
+    let err = detached.into_js_error(&mut rt);
+    let out = check.call(std::iter::once(err), &mut rt).unwrap();
+    assert!(out.try_into_js::<bool>(&mut rt).unwrap());
+}
+
+#[test]
+fn host_fn_throws_typed_error() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let host = JsiFn::from_host_fn(
+        &PropName::new("host", &mut rt),
+        1,
+        Box::new(move |_this, args, _rt| {
+            let err = HostError::range_error("index 7 is out of bounds")
+                .with_code("ERR_OUT_OF_BOUNDS")
+                .with_source(anyhow::anyhow!("buffer is empty").context("reading buffer"));
+
+            if args.is_empty() {
+                Err(err.into())
+            } else {
+                Err(anyhow::Error::from(err)).context("loading asset")
+            }
+        }),
+        &mut rt,
+    );
+
+    let check = eval_fn(
+        "(function (host) {
+            try { host() } catch (e) {
+                if (!(e instanceof RangeError) || e.code !== 'ERR_OUT_OF_BOUNDS') return 1
+                if (e.message !== 'index 7 is out of bounds') return 2
+                if (e.cause.message !== 'reading buffer') return 3
+                if (e.cause.cause.message !== 'buffer is empty') return 4
+            }
+            try { host(1) } catch (e) {
+                if (!(e instanceof RangeError) || e.message !== 'loading asset') return 5
+                if (!(e.cause instanceof RangeError)) return 6
+                if (e.cause.cause.cause.message !== 'buffer is empty') return 7
+                return 0
+            }
+            return 8
+        })",
+        &mut rt,
+    );
+
+    let host = JsiObject::from(host).into_value(&mut rt);
+    let out = check.call(std::iter::once(host), &mut rt).unwrap();
+    assert_eq!(0., out.try_into_js::<f64>(&mut rt).unwrap());
+}
+
+#[test]
+fn host_fn_throws_without_error_constructors() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let host = JsiFn::from_host_fn(
+        &PropName::new("host", &mut rt),
+        0,
+        Box::new(move |_this, _args, _rt| {
+            Err(HostError::range_error("index 7 is out of bounds")
+                .with_source(anyhow::anyhow!("buffer is empty"))
+                .into())
+        }),
+        &mut rt,
+    );
+
+    let check = eval_fn(
+        "(function (host) {
+            delete globalThis.Error;
+            delete globalThis.RangeError;
+            try { host() } catch (e) {
+                return e.name === 'RangeError' && e.message === 'index 7 is out of bounds'
+                    && e.cause.message === 'buffer is empty'
+            }
+            return false
+        })",
+        &mut rt,
+    );
+
+    let host = JsiObject::from(host).into_value(&mut rt);
+    let out = check.call(std::iter::once(host), &mut rt).unwrap();
+    assert_eq!(Some(true), out.try_into_js::<bool>(&mut rt));
+}
+
+#[test]
+fn host_fn_panic_is_thrown() {
+    static CAUGHT: AtomicUsize = AtomicUsize::new(0);
+
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    jsi::set_panic_hook(|panic| {
+        if panic.message == "host fn exploded" {
+            CAUGHT.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    let host = JsiFn::from_host_fn(
+        &PropName::new("host", &mut rt),
+        0,
+        Box::new(move |_this, _args, _rt| panic!("host fn exploded")),
+        &mut rt,
+    );
+
+    let check = eval_fn(
+        "(function (host) {
+            try { host() } catch (e) { return e.message }
+            return 'no exception'
+        })",
+        &mut rt,
+    );
+
+    let host = JsiObject::from(host).into_value(&mut rt);
+    let out = check.call(std::iter::once(host), &mut rt).unwrap();
+    let out: JsiString = out.try_into_js(&mut rt).unwrap();
+    assert!(rt.to_string(&out).contains("host fn exploded"));
+    assert_eq!(1, CAUGHT.load(Ordering::SeqCst));
+
+    jsi::take_panic_hook();
+}
@@ -0,0 +1,120 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use jsi::{EvaluateError, JsiBuffer, JsiString, RuntimeHandle, ScriptCache};
+
+mod common;
+
+#[test]
+fn evaluate_script() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let out = rt.evaluate("1 + 1", "test.js").unwrap();
+    let out: f64 = out.try_into_js(&mut rt).unwrap();
+    assert_eq!(2., out);
+
+    let out = rt.evaluate("'hello'", "test.js").unwrap();
+    let out: JsiString = out.try_into_js(&mut rt).unwrap();
+    assert_eq!("hello", rt.to_string(&out));
+}
+
+#[test]
+fn evaluate_thrown_error() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    match rt.evaluate("throw new TypeError('oops')", "test.js") {
+        Err(EvaluateError::Thrown(err)) => {
+            assert_eq!("TypeError", err.name());
+            assert_eq!("oops", err.message());
+            assert!(err.value(&mut rt).unwrap().is_object());
+        }
+        other => panic!("expected thrown error, got {:?}", other),
+    }
+
+    match rt.evaluate("throw 42", "test.js") {
+        Err(EvaluateError::Thrown(err)) => assert_eq!("42", err.message()),
+        other => panic!("expected thrown error, got {:?}", other),
+    }
+}
+
+#[test]
+fn evaluate_syntax_error() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    match rt.evaluate("let x = 1;\nlet = = 2;", "broken.js") {
+        Err(EvaluateError::Syntax(err)) => {
+            assert_eq!("broken.js", err.source_url);
+            assert_eq!(2, err.line);
+        }
+        other => panic!("expected syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn evaluate_rust_buffer() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    static SCRIPT: &[u8] = b"[1, 2, 3].reduce((a, b) => a + b)";
+    let buffer = JsiBuffer::from(SCRIPT);
+    assert_eq!(SCRIPT, buffer.as_bytes());
+
+    let out = rt.evaluate_buffer(&buffer, "static.js").unwrap();
+    let out: f64 = out.try_into_js(&mut rt).unwrap();
+    assert_eq!(6., out);
+
+    let buffer = JsiBuffer::from(String::from("'owned' + ' source'"));
+    let out = rt.evaluate_buffer(&buffer, "owned.js").unwrap();
+    let out: JsiString = out.try_into_js(&mut rt).unwrap();
+    assert_eq!("owned source", rt.to_string(&out));
+}
+
+/// Returns a shorter slice every time `as_ref` is called.
+struct Shrinking(AtomicUsize);
+
+impl AsRef<[u8]> for Shrinking {
+    fn as_ref(&self) -> &[u8] {
+        static BYTES: &[u8] = b"1 + 1; 2 + 2";
+        let calls = self.0.fetch_add(1, Ordering::SeqCst);
+        &BYTES[..BYTES.len().saturating_sub(calls)]
+    }
+}
+
+#[test]
+fn rust_buffer_reads_bytes_once() {
+    let buffer = JsiBuffer::new(Shrinking(AtomicUsize::new(0)));
+    assert_eq!(b"1 + 1; 2 + 2", buffer.as_bytes());
+    assert_eq!(12, buffer.len());
+    assert_eq!(b"1 + 1; 2 + 2", buffer.as_bytes());
+}
+
+#[test]
+fn prepared_script_cache() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    rt.evaluate("globalThis.counter = 0", "init.js").unwrap();
+
+    let mut cache = ScriptCache::new();
+    let helper = JsiBuffer::from("++globalThis.counter");
+
+    for i in 1..=3 {
+        let out = cache.evaluate(&helper, "helper.js", &mut rt).unwrap();
+        let out: f64 = out.try_into_js(&mut rt).unwrap();
+        assert_eq!(i as f64, out);
+    }
+
+    // same contents in a different buffer should hit the cache
+    let copy = JsiBuffer::from(String::from("++globalThis.counter"));
+    cache.evaluate(&copy, "helper.js", &mut rt).unwrap();
+    assert_eq!(1, cache.len());
+
+    match cache.prepare(&JsiBuffer::from("(("), "broken.js", &mut rt) {
+        Err(EvaluateError::Syntax(err)) => assert_eq!(1, err.line),
+        Err(other) => panic!("expected syntax error, got {:?}", other),
+        Ok(_) => panic!("expected syntax error"),
+    }
+    assert_eq!(1, cache.len());
+}
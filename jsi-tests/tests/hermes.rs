@@ -0,0 +1,169 @@
+use jsi::{JsiFn, JsiValue, RuntimeHandle};
+use jsi_hermes::{HermesRuntime, Profiler, RuntimeConfig};
+
+fn global_bool(rt: &mut RuntimeHandle, expr: &str) -> bool {
+    let out = rt.evaluate(expr, "check.js").unwrap();
+    out.try_into_js(rt).unwrap()
+}
+
+#[test]
+fn default_runtime() {
+    let mut runtime = HermesRuntime::new();
+    let mut rt = runtime.handle();
+
+    let out = rt.evaluate("6 * 7", "test.js").unwrap();
+    let out: f64 = out.try_into_js(&mut rt).unwrap();
+    assert_eq!(42., out);
+}
+
+#[test]
+fn es6_proxy_disabled() {
+    let config = RuntimeConfig::builder().es6_proxy(false).build();
+    let mut runtime = HermesRuntime::with_config(&config).unwrap();
+    let mut rt = runtime.handle();
+
+    assert!(global_bool(&mut rt, "typeof Proxy === 'undefined'"));
+}
+
+#[test]
+fn microtask_queue() {
+    let config = RuntimeConfig::builder().microtask_queue(true).build();
+    let mut runtime = HermesRuntime::with_config(&config).unwrap();
+    let mut rt = runtime.handle();
+
+    rt.evaluate(
+        "globalThis.log = []; Promise.resolve().then(() => log.push('promise'))",
+        "test.js",
+    )
+    .unwrap();
+
+    rt.queue_microtask(|rt| {
+        rt.evaluate("log.push('rust')", "task.js")
+            .map_err(|err| anyhow::anyhow!("{}", err))?;
+        Ok(())
+    })
+    .unwrap();
+
+    assert!(rt.drain_microtasks(None).unwrap());
+    assert!(global_bool(
+        &mut rt,
+        "log.length === 2 && log[0] === 'promise' && log[1] === 'rust'"
+    ));
+}
+
+#[test]
+fn heap_info_and_gc() {
+    let mut runtime = HermesRuntime::new();
+    let mut rt = runtime.handle();
+
+    rt.evaluate(
+        "globalThis.junk = Array.from({ length: 10000 }, (_, i) => ({ i }))",
+        "test.js",
+    )
+    .unwrap();
+
+    let before = rt.heap_info();
+    assert!(before.allocated_bytes.unwrap() > 0);
+    assert!(!before.raw.is_empty());
+
+    rt.evaluate("globalThis.junk = undefined", "test.js")
+        .unwrap();
+    rt.collect_garbage("test");
+
+    let after = rt.heap_info();
+    assert!(after.num_collections.unwrap() > before.num_collections.unwrap());
+    assert!(after.allocated_bytes.unwrap() < before.allocated_bytes.unwrap());
+}
+
+#[test]
+fn heap_snapshot_to_writer() {
+    let mut runtime = HermesRuntime::new();
+    let mut rt = runtime.handle();
+
+    let mut snapshot = Vec::new();
+    rt.create_heap_snapshot_to_writer(&mut snapshot).unwrap();
+
+    let snapshot = String::from_utf8(snapshot).unwrap();
+    assert!(snapshot.starts_with("{\"snapshot\":"));
+    assert!(snapshot.trim_end().ends_with('}'));
+}
+
+#[test]
+fn heap_snapshot_to_file() {
+    let mut runtime = HermesRuntime::new();
+    let mut rt = runtime.handle();
+
+    let path = std::env::temp_dir().join(format!(
+        "jsi-tests-{}-heap_snapshot_to_file.heapsnapshot",
+        std::process::id()
+    ));
+    rt.create_heap_snapshot_to_file(&path).unwrap();
+
+    let snapshot = std::fs::read_to_string(&path);
+    std::fs::remove_file(&path).unwrap();
+    let snapshot = snapshot.unwrap();
+
+    // parse it with the runtime's own JSON parser
+    let parse = rt
+        .evaluate(
+            "(function (text) { const s = JSON.parse(text); \
+               return typeof s.snapshot === 'object' && Array.isArray(s.nodes) && s.nodes.length > 0 })",
+            "check.js",
+        )
+        .unwrap();
+    let parse: JsiFn = parse.try_into_js(&mut rt).unwrap();
+    let snapshot = JsiValue::new_string(&snapshot, &mut rt);
+    let out = parse.call(std::iter::once(snapshot), &mut rt).unwrap();
+    assert!(out.try_into_js::<bool>(&mut rt).unwrap());
+}
+
+#[test]
+fn create_heap_snapshot_to_file_bad_path() {
+    let mut runtime = HermesRuntime::new();
+    let mut rt = runtime.handle();
+
+    let path = std::env::temp_dir()
+        .join("jsi-tests-missing-directory")
+        .join("out.heapsnapshot");
+    assert!(rt.create_heap_snapshot_to_file(&path).is_err());
+}
+
+#[test]
+fn sampling_profiler() {
+    let mut runtime = HermesRuntime::new();
+    runtime.register_for_profiling().unwrap();
+
+    Profiler::enable().unwrap();
+    runtime
+        .handle()
+        .evaluate(
+            "function busy() { let x = 0; for (let i = 0; i < 5e6; i++) x += i; return x }
+             busy()",
+            "busy.js",
+        )
+        .unwrap();
+    Profiler::disable().unwrap();
+
+    let mut trace = Vec::new();
+    Profiler::dump_to_writer(&mut trace).unwrap();
+    runtime.unregister_for_profiling().unwrap();
+
+    let trace = String::from_utf8(trace).unwrap();
+    assert!(trace.contains("traceEvents"));
+}
+
+#[test]
+fn drop_registered_runtime() {
+    // dropping a runtime that is still registered unregisters it, so the
+    // profiler doesn't sample a destroyed runtime
+    let mut runtime = HermesRuntime::new();
+    runtime.register_for_profiling().unwrap();
+    runtime.register_for_profiling().unwrap();
+    drop(runtime);
+
+    let mut runtime = HermesRuntime::new();
+    runtime.register_for_profiling().unwrap();
+    runtime.handle().evaluate("1 + 1", "test.js").unwrap();
+    runtime.unregister_for_profiling().unwrap();
+    runtime.unregister_for_profiling().unwrap();
+}
@@ -0,0 +1,31 @@
+use jsi::{DeserializeValue, RuntimeHandle};
+
+mod common;
+
+#[test]
+fn integer_conversion_is_checked() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let n = rt.evaluate("255", "test.js").unwrap();
+    assert_eq!(Some(255), n.try_into_js::<u8>(&mut rt));
+    let n = rt.evaluate("256", "test.js").unwrap();
+    assert_eq!(None, n.try_into_js::<u8>(&mut rt));
+    let n = rt.evaluate("-1", "test.js").unwrap();
+    assert_eq!(None, n.try_into_js::<u32>(&mut rt));
+    let n = rt.evaluate("1.5", "test.js").unwrap();
+    assert_eq!(None, n.try_into_js::<i32>(&mut rt));
+    let n = rt.evaluate("NaN", "test.js").unwrap();
+    assert_eq!(None, n.try_into_js::<i64>(&mut rt));
+    let n = rt.evaluate("2 ** 63", "test.js").unwrap();
+    assert_eq!(None, n.try_into_js::<i64>(&mut rt));
+    let n = rt.evaluate("-(2 ** 63)", "test.js").unwrap();
+    assert_eq!(Some(i64::MIN), n.try_into_js::<i64>(&mut rt));
+
+    let n = rt.evaluate("300", "test.js").unwrap();
+    let err = u8::deserialize_value(n, &mut rt).unwrap_err();
+    assert_eq!("300 is out of range for u8", err.to_string());
+    let n = rt.evaluate("0.5", "test.js").unwrap();
+    let err = i16::deserialize_value(n, &mut rt).unwrap_err();
+    assert_eq!("expected an integer, got 0.5", err.to_string());
+}
@@ -0,0 +1,49 @@
+use jsi::{js_array, js_object, IntoValue, JsiFn, JsiValue, RuntimeHandle};
+
+mod common;
+
+#[test]
+fn object_and_array_literals() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    let callback: JsiFn = rt
+        .evaluate("() => 'called'", "test.js")
+        .unwrap()
+        .try_into_js(rt)
+        .unwrap();
+    let count = 3;
+
+    let obj = js_object!(rt, {
+        name: "x",
+        count: count,
+        nested: { ok: true, list: [1, 2, { deep: [] }] },
+        "content-type": JsiValue::new_string("text/plain", rt),
+        2: JsiValue::new_null(),
+        empty: {},
+        cb: callback,
+    });
+    let arr = js_array!(rt, ["a", { b: 2.5 }, [3, 4], vec![5, 6]]);
+
+    let check: JsiFn = rt
+        .evaluate(
+            "(obj, arr) => JSON.stringify(obj) === JSON.stringify({
+                    2: null,
+                    name: 'x',
+                    count: 3,
+                    nested: { ok: true, list: [1, 2, { deep: [] }] },
+                    'content-type': 'text/plain',
+                    empty: {},
+                })
+                && obj.cb() === 'called'
+                && JSON.stringify(arr) === '[\"a\",{\"b\":2.5},[3,4],[5,6]]'",
+            "test.js",
+        )
+        .unwrap()
+        .try_into_js(rt)
+        .unwrap();
+    let args = [obj.into_value(rt), arr.into_value(rt)];
+    let out = check.call(args, rt).unwrap();
+    assert_eq!(Some(true), out.try_into_js::<bool>(rt));
+}
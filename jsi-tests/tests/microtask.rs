@@ -0,0 +1,94 @@
+use jsi::RuntimeHandle;
+use jsi_hermes::{HermesRuntime, RuntimeConfig};
+
+fn global_bool(rt: &mut RuntimeHandle, expr: &str) -> bool {
+    let out = rt.evaluate(expr, "check.js").unwrap();
+    out.try_into_js(rt).unwrap()
+}
+
+fn runtime() -> HermesRuntime {
+    let config = RuntimeConfig::builder().microtask_queue(true).build();
+    HermesRuntime::with_config(&config).unwrap()
+}
+
+#[test]
+fn microtasks_run_when_drained() {
+    let mut runtime = runtime();
+    let mut rt = runtime.handle();
+
+    rt.evaluate("globalThis.log = []", "test.js").unwrap();
+    for _ in 0..3 {
+        rt.queue_microtask(|rt| {
+            rt.evaluate("log.push(log.length)", "task.js")
+                .map_err(|err| anyhow::anyhow!("{}", err))?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    assert!(global_bool(&mut rt, "log.length === 0"));
+    assert!(rt.drain_microtasks(None).unwrap());
+    assert!(global_bool(&mut rt, "log.join() === '0,1,2'"));
+}
+
+#[test]
+fn microtask_error_is_returned() {
+    let mut runtime = runtime();
+    let mut rt = runtime.handle();
+
+    rt.evaluate("globalThis.log = []", "test.js").unwrap();
+    rt.queue_microtask(|_rt| anyhow::bail!("task failed"))
+        .unwrap();
+    rt.queue_microtask(|rt| {
+        rt.evaluate("log.push('after')", "task.js")
+            .map_err(|err| anyhow::anyhow!("{}", err))?;
+        Ok(())
+    })
+    .unwrap();
+
+    let err = rt.drain_microtasks(None).unwrap_err();
+    assert!(err.to_string().contains("task failed"));
+
+    // the rest of the queue is still there
+    assert!(rt.drain_microtasks(None).unwrap());
+    assert!(global_bool(&mut rt, "log.length === 1"));
+}
+
+#[test]
+fn queue_microtask_falls_back_to_promise() {
+    let mut runtime = runtime();
+    let mut rt = runtime.handle();
+
+    rt.evaluate(
+        "globalThis.log = []; delete globalThis.queueMicrotask",
+        "test.js",
+    )
+    .unwrap();
+    rt.queue_microtask(|rt| {
+        rt.evaluate("log.push('rust')", "task.js")
+            .map_err(|err| anyhow::anyhow!("{}", err))?;
+        Ok(())
+    })
+    .unwrap();
+
+    assert!(rt.drain_microtasks(None).unwrap());
+    assert!(global_bool(
+        &mut rt,
+        "log.length === 1 && log[0] === 'rust'"
+    ));
+}
+
+#[test]
+fn queue_microtask_without_promise_fails() {
+    let mut runtime = runtime();
+    let mut rt = runtime.handle();
+
+    rt.evaluate(
+        "delete globalThis.queueMicrotask; globalThis.Promise = undefined",
+        "test.js",
+    )
+    .unwrap();
+
+    let err = rt.queue_microtask(|_rt| Ok(())).unwrap_err();
+    assert_eq!("TypeError", err.name());
+}
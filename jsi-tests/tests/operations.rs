@@ -0,0 +1,121 @@
+use jsi::{JsiObject, JsiValue, PreferredType, PropName, RuntimeHandle};
+
+mod common;
+
+const VALUES: &str = "[
+    undefined, null, true, false, 0, -0, NaN, 1, 1e-20, Infinity, '', '0', ' 12\\n',
+    '0x1f', 'abc', 'Infinity', 0n, 1n, 12n, 2n ** 64n, Symbol('s'), {}, [], [1], ['12'],
+    { valueOf() { return 12 } }, { [Symbol.toPrimitive]: (hint) => hint }, function () {},
+]";
+
+fn prop<'rt>(obj: &JsiObject<'rt>, name: &str, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+    obj.get(PropName::new(name, rt), rt)
+}
+
+#[test]
+fn operations_match_js() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    let src = format!(
+        "(() => {{
+            const values = {};
+            const attempt = (f) => {{ try {{ return f() }} catch {{ return null }} }};
+            return {{
+                values,
+                truthy: values.map((v) => !!v),
+                types: values.map((v) => typeof v),
+                numbers: values.map((v) => attempt(() => [+v])),
+                loose: values.map((a) => values.map((b) => a == b)),
+                strict: values.map((a) => values.map((b) => a === b)),
+                sameValueZero: values.map((a) => values.map((b) => [a].includes(b))),
+            }}
+        }})()",
+        VALUES
+    );
+    let expected: JsiObject = rt.evaluate(&src, "test.js").unwrap().into_js(rt);
+
+    let values: Vec<JsiValue> = prop(&expected, "values", rt).convert(rt).unwrap();
+    let truthy: Vec<bool> = prop(&expected, "truthy", rt).convert(rt).unwrap();
+    let types: Vec<String> = prop(&expected, "types", rt).convert(rt).unwrap();
+    let numbers: Vec<Option<Vec<f64>>> = prop(&expected, "numbers", rt).convert(rt).unwrap();
+    let loose: Vec<Vec<bool>> = prop(&expected, "loose", rt).convert(rt).unwrap();
+    let strict: Vec<Vec<bool>> = prop(&expected, "strict", rt).convert(rt).unwrap();
+    let same_value_zero: Vec<Vec<bool>> = prop(&expected, "sameValueZero", rt).convert(rt).unwrap();
+
+    for (i, a) in values.iter().enumerate() {
+        assert_eq!(truthy[i], a.to_boolean(rt), "ToBoolean of value {}", i);
+        assert_eq!(types[i], a.type_of(rt), "typeof value {}", i);
+
+        match (&numbers[i], a.to_number(rt)) {
+            (Some(n), Ok(m)) => assert!(
+                n[0] == m || (n[0].is_nan() && m.is_nan()),
+                "ToNumber of value {}",
+                i
+            ),
+            (None, Err(_)) => {}
+            (n, m) => panic!("ToNumber of value {}: expected {:?}, got {:?}", i, n, m),
+        }
+
+        for (j, b) in values.iter().enumerate() {
+            assert_eq!(
+                loose[i][j],
+                a.loose_equals(b, rt).unwrap(),
+                "value {} == value {}",
+                i,
+                j
+            );
+            assert_eq!(
+                strict[i][j],
+                a.strict_equals(b, rt),
+                "value {} === value {}",
+                i,
+                j
+            );
+            assert_eq!(
+                same_value_zero[i][j],
+                a.same_value_zero(b, rt),
+                "SameValueZero(value {}, value {})",
+                i,
+                j
+            );
+        }
+    }
+
+    // the hint is passed to Symbol.toPrimitive
+    let exotic = &values[26];
+    let hint = exotic.to_primitive(PreferredType::String, rt).unwrap();
+    assert_eq!(Some("string".to_string()), hint.try_into_js(rt));
+    let hint = exotic.to_primitive(PreferredType::Default, rt).unwrap();
+    assert_eq!(Some("default".to_string()), hint.try_into_js(rt));
+}
+
+#[test]
+fn operations_with_replaced_globals() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    let values: Vec<JsiValue> = rt
+        .evaluate("['12', 1n, 1, '1']", "test.js")
+        .unwrap()
+        .convert(rt)
+        .unwrap();
+    rt.evaluate(
+        "globalThis.Number = () => 'not a number'; delete globalThis.BigInt",
+        "test.js",
+    )
+    .unwrap();
+
+    let err = values[0].to_number(rt).unwrap_err();
+    assert_eq!("TypeError", err.name());
+    let err = values[1].loose_equals(&values[2], rt).unwrap_err();
+    assert_eq!("TypeError", err.name());
+    let err = values[1].loose_equals(&values[3], rt).unwrap_err();
+    assert_eq!("TypeError", err.name());
+
+    rt.evaluate("delete globalThis.Number", "test.js").unwrap();
+    let err = values[0].to_number(rt).unwrap_err();
+    assert_eq!("TypeError", err.name());
+}
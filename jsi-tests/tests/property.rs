@@ -0,0 +1,222 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use jsi::{
+    AsValue, JsiFn, JsiObject, JsiSymbol, JsiValue, PropName, PropertyDescriptor, PropertyValue,
+    RuntimeHandle,
+};
+
+mod common;
+
+fn check<'rt>(src: &str, obj: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> bool {
+    let f: JsiFn = rt
+        .evaluate(src, "test.js")
+        .unwrap()
+        .try_into_js(rt)
+        .unwrap();
+    let arg = obj.as_value(rt);
+    f.call(std::iter::once(arg), rt)
+        .unwrap()
+        .try_into_js(rt)
+        .unwrap()
+}
+
+#[test]
+fn define_and_delete_properties() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    let mut obj = JsiObject::new(rt);
+
+    let version = PropertyDescriptor::data(JsiValue::new_number(2.));
+    obj.define_property(PropName::new("version", rt), version, rt)
+        .unwrap();
+
+    let stored = Rc::new(Cell::new(1.));
+    let (get, set) = (stored.clone(), stored.clone());
+    let count = PropertyDescriptor::getter(move |_, _| Ok(JsiValue::new_number(get.get())), rt)
+        .with_setter(
+            move |_, value, rt| {
+                set.set(value.convert(rt)?);
+                Ok(())
+            },
+            rt,
+        )
+        .enumerable(true)
+        .configurable(true);
+    obj.define_property(PropName::new("count", rt), count, rt)
+        .unwrap();
+
+    let hidden = PropertyDescriptor::data(JsiValue::new_bool(true)).configurable(true);
+    let key = PropName::from_symbol(JsiSymbol::for_key("hidden", rt).unwrap(), rt);
+    obj.define_property(key, hidden, rt).unwrap();
+
+    assert!(check(
+        "(obj) => {
+            'use strict';
+            try { obj.version = 3; return false } catch (e) {}
+            obj.count = obj.count + 4;
+            return obj.version === 2
+                && obj.count === 5
+                && obj[Symbol.for('hidden')] === true
+                && JSON.stringify(Object.keys(obj)) === '[\"count\"]'
+        }",
+        &obj,
+        rt
+    ));
+    assert_eq!(5., stored.get());
+
+    let descriptor = obj
+        .get_own_property_descriptor(PropName::new("version", rt), rt)
+        .unwrap()
+        .unwrap();
+    assert!(!descriptor.enumerable && !descriptor.configurable);
+    match descriptor.value {
+        PropertyValue::Data { value, writable } => {
+            assert!(!writable);
+            assert_eq!(Some(2.), value.try_into_js::<f64>(rt));
+        }
+        PropertyValue::Accessor { .. } => panic!("expected a data property"),
+    }
+
+    let descriptor = obj
+        .get_own_property_descriptor(PropName::new("count", rt), rt)
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        descriptor.value,
+        PropertyValue::Accessor {
+            get: Some(_),
+            set: Some(_)
+        }
+    ));
+    assert!(obj
+        .get_own_property_descriptor(PropName::new("toString", rt), rt)
+        .unwrap()
+        .is_none());
+
+    assert!(!obj.delete(PropName::new("version", rt), rt).unwrap());
+    assert!(obj.delete(PropName::new("count", rt), rt).unwrap());
+    let key = PropName::from_symbol(JsiSymbol::for_key("hidden", rt).unwrap(), rt);
+    assert!(obj.delete(key, rt).unwrap());
+    assert!(check(
+        "(obj) => Reflect.ownKeys(obj).length === 1",
+        &obj,
+        rt
+    ));
+}
+
+#[test]
+fn prototypes_and_integrity() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    let mut proto = JsiObject::new(rt);
+    proto.set(
+        PropName::new("greeting", rt),
+        &JsiValue::new_string("hi", rt),
+        rt,
+    );
+
+    let mut obj = JsiObject::with_prototype(Some(&proto), rt).unwrap();
+    let greeting: Option<String> = obj.get(PropName::new("greeting", rt), rt).try_into_js(rt);
+    assert_eq!(Some("hi".to_string()), greeting);
+    let found = obj.get_prototype(rt).unwrap().unwrap().as_value(rt);
+    assert!(proto.as_value(rt).strict_equals(&found, rt));
+
+    obj.set_prototype(None, rt).unwrap();
+    assert!(obj.get_prototype(rt).unwrap().is_none());
+    assert!(!obj.has(PropName::new("greeting", rt), rt));
+
+    // a cycle of prototypes is rejected
+    obj.set_prototype(Some(&proto), rt).unwrap();
+    assert!(proto.set_prototype(Some(&obj), rt).is_err());
+
+    proto.seal(rt).unwrap();
+    assert!(proto.is_sealed(rt).unwrap());
+    assert!(!proto.is_frozen(rt).unwrap());
+
+    obj.freeze(rt).unwrap();
+    assert!(obj.is_frozen(rt).unwrap());
+    assert!(obj.set_prototype(None, rt).is_err());
+}
+
+#[test]
+fn proto_property_name() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    rt.evaluate(
+        "Object.defineProperty(Object.prototype, 'trap', { set() { throw new Error('trap') } })",
+        "test.js",
+    )
+    .unwrap();
+
+    let mut obj = JsiObject::new(rt);
+    let proto = PropertyDescriptor::data(JsiValue::new_number(1.)).configurable(true);
+    obj.define_property(PropName::new("__proto__", rt), proto, rt)
+        .unwrap();
+    let trap = PropertyDescriptor::data(JsiValue::new_number(2.)).configurable(true);
+    obj.define_property(PropName::new("trap", rt), trap, rt)
+        .unwrap();
+
+    assert!(check(
+        "(obj) => Object.getPrototypeOf(obj) === Object.prototype
+            && Object.getOwnPropertyNames(obj).join() === '__proto__,trap'
+            && Object.getOwnPropertyDescriptor(obj, '__proto__').value === 1",
+        &obj,
+        rt
+    ));
+
+    let desc = obj
+        .get_own_property_descriptor(PropName::new("__proto__", rt), rt)
+        .unwrap()
+        .unwrap();
+    match desc.value {
+        PropertyValue::Data { value, .. } => assert_eq!(Some(1.), value.try_into_js(rt)),
+        _ => panic!("expected a data property"),
+    }
+
+    assert!(obj.delete(PropName::new("__proto__", rt), rt).unwrap());
+    assert!(check(
+        "(obj) => Object.getOwnPropertyNames(obj).join() === 'trap'",
+        &obj,
+        rt
+    ));
+}
+
+#[test]
+fn replaced_builtins_are_errors() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let rt = &mut rt;
+
+    let mut obj = JsiObject::new(rt);
+    obj.set(PropName::new("a", rt), &JsiValue::new_number(1.), rt);
+
+    rt.evaluate("delete Object.prototype.propertyIsEnumerable", "test.js")
+        .unwrap();
+    let err = obj.keys(rt).err().unwrap();
+    assert_eq!("TypeError", err.name());
+
+    rt.evaluate("Reflect.ownKeys = () => [1, 2]", "test.js")
+        .unwrap();
+    let err = obj
+        .get_own_property_descriptor(PropName::new("a", rt), rt)
+        .err()
+        .unwrap();
+    assert_eq!("TypeError", err.name());
+
+    rt.evaluate(
+        "delete globalThis.Reflect; globalThis.Object = 1",
+        "test.js",
+    )
+    .unwrap();
+    let err = obj.delete(PropName::new("a", rt), rt).err().unwrap();
+    assert_eq!("TypeError", err.name());
+    assert!(obj.freeze(rt).is_err());
+    assert!(JsiObject::with_prototype(None, rt).is_err());
+}
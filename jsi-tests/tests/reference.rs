@@ -0,0 +1,180 @@
+use jsi::{JsiFn, JsiObject, JsiRef, JsiRefError, JsiValue, PropName, RuntimeHandle};
+
+mod common;
+
+#[test]
+fn reference_outlives_handle() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let callback: JsiRef<JsiFn<'static>> = {
+        let f: JsiFn = rt
+            .evaluate("(x) => x * 2", "test.js")
+            .unwrap()
+            .try_into_js(&mut rt)
+            .unwrap();
+        JsiRef::new(f, &mut rt).unwrap()
+    };
+
+    rt.collect_garbage("test");
+
+    let f = callback.get(&mut rt).unwrap();
+    let out = f
+        .call(std::iter::once(JsiValue::new_number(21.)), &mut rt)
+        .unwrap();
+    assert_eq!(Some(42.), out.try_into_js::<f64>(&mut rt));
+
+    // dropped references are released, and their slots reused
+    let mut obj = JsiObject::new(&mut rt);
+    obj.set(
+        PropName::new("x", &mut rt),
+        &JsiValue::new_number(1.),
+        &mut rt,
+    );
+    let first: JsiRef<JsiObject<'static>> = JsiRef::new(obj, &mut rt).unwrap();
+    drop(first);
+    let second: JsiRef<JsiValue<'static>> = JsiRef::new(JsiValue::new_null(), &mut rt).unwrap();
+    assert!(second.get(&mut rt).unwrap().is_null());
+    assert!(callback.get(&mut rt).is_ok());
+}
+
+#[test]
+fn reference_errors_without_runtime() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+    let value: JsiRef<JsiValue<'static>> = JsiRef::new(JsiValue::new_number(1.), &mut rt).unwrap();
+
+    let other = common::create_raw_runtime();
+    let mut other_rt = RuntimeHandle::new_unchecked(other.as_mut_ptr());
+    assert_eq!(
+        JsiRefError::RegistryMissing,
+        value.get(&mut other_rt).err().unwrap()
+    );
+
+    let _other_value: JsiRef<JsiValue<'static>> =
+        JsiRef::new(JsiValue::new_null(), &mut other_rt).unwrap();
+    assert_eq!(
+        JsiRefError::WrongRuntime,
+        value.get(&mut other_rt).err().unwrap()
+    );
+
+    drop(raw);
+    assert_eq!(
+        JsiRefError::RuntimeDestroyed,
+        value.get(&mut other_rt).err().unwrap()
+    );
+}
+
+#[test]
+fn reference_without_symbol_for() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    rt.evaluate("delete globalThis.Symbol", "test.js").unwrap();
+    assert_eq!(
+        JsiRefError::SymbolUnavailable,
+        JsiRef::<JsiValue<'static>>::new(JsiValue::new_null(), &mut rt)
+            .err()
+            .unwrap()
+    );
+}
+
+#[test]
+fn reference_without_reflect() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    rt.evaluate("delete globalThis.Reflect", "test.js").unwrap();
+    assert_eq!(
+        JsiRefError::RegistryUnavailable,
+        JsiRef::<JsiValue<'static>>::new(JsiValue::new_null(), &mut rt)
+            .err()
+            .unwrap()
+    );
+}
+
+#[test]
+fn reference_registry_is_protected() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let value: JsiRef<JsiFn<'static>> = {
+        let f: JsiFn = rt
+            .evaluate("() => 1", "test.js")
+            .unwrap()
+            .try_into_js(&mut rt)
+            .unwrap();
+        JsiRef::new(f, &mut rt).unwrap()
+    };
+
+    let out = rt
+        .evaluate(
+            "const key = Symbol.for('jsi-rs.references');
+            const desc = Object.getOwnPropertyDescriptor(globalThis, key);
+            const deleted = delete globalThis[key];
+            globalThis[key] = {};
+            !desc.enumerable && !desc.writable && !desc.configurable && !deleted",
+            "test.js",
+        )
+        .unwrap();
+    assert_eq!(Some(true), out.try_into_js::<bool>(&mut rt));
+    assert!(value.get(&mut rt).is_ok());
+
+    // the slots of the registry can still be overwritten
+    rt.evaluate(
+        "globalThis[Symbol.for('jsi-rs.references')][0] = 'not a function'",
+        "test.js",
+    )
+    .unwrap();
+    assert_eq!(JsiRefError::WrongType, value.get(&mut rt).err().unwrap());
+}
+
+#[test]
+fn reference_registry_key_taken() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    rt.evaluate(
+        "Object.defineProperty(globalThis, Symbol.for('jsi-rs.references'), { value: 1 })",
+        "test.js",
+    )
+    .unwrap();
+    assert_eq!(
+        JsiRefError::RegistryUnavailable,
+        JsiRef::<JsiValue<'static>>::new(JsiValue::new_null(), &mut rt)
+            .err()
+            .unwrap()
+    );
+}
+
+#[test]
+fn reference_registry_frozen() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let first: JsiRef<JsiValue<'static>> = JsiRef::new(JsiValue::new_number(1.), &mut rt).unwrap();
+    let second: JsiRef<JsiValue<'static>> = JsiRef::new(JsiValue::new_number(2.), &mut rt).unwrap();
+    drop(first);
+
+    let out = rt
+        .evaluate(
+            "const store = globalThis[Symbol.for('jsi-rs.references')];
+            const token = Object.getOwnPropertyDescriptor(store, 'token');
+            Object.freeze(store);
+            Object.getPrototypeOf(store) === null && !token.writable && !token.configurable",
+            "test.js",
+        )
+        .unwrap();
+    assert_eq!(Some(true), out.try_into_js::<bool>(&mut rt));
+
+    assert_eq!(
+        JsiRefError::RegistryUnavailable,
+        JsiRef::<JsiValue<'static>>::new(JsiValue::new_null(), &mut rt)
+            .err()
+            .unwrap()
+    );
+    assert_eq!(
+        JsiRefError::RegistryUnavailable,
+        second.get(&mut rt).err().unwrap()
+    );
+}
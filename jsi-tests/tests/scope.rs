@@ -0,0 +1,33 @@
+use jsi::{IntoValue, JsiArray, JsiFn, JsiString, JsiValue, RuntimeHandle};
+
+mod common;
+
+#[test]
+fn scope_in_loop() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let mut arr = JsiArray::new(10_000, &mut rt);
+    for i in 0..10_000 {
+        rt.scope(|rt| {
+            let item = JsiValue::new_string(&format!("item {}", i), rt);
+            arr.set(i, &item, rt);
+        });
+    }
+
+    // values created inside of a scope are still valid after it is popped
+    let last: JsiString = rt.scope(|rt| JsiString::new("last", rt));
+    assert_eq!("last", rt.to_string(&last));
+
+    let check = rt
+        .evaluate(
+            "(function (arr) { return arr.length === 10000 && arr[9999] === 'item 9999' })",
+            "test.js",
+        )
+        .unwrap();
+    let check: JsiFn = check.try_into_js(&mut rt).unwrap();
+
+    let arr = arr.into_value(&mut rt);
+    let out = check.call(std::iter::once(arr), &mut rt).unwrap();
+    assert!(out.try_into_js::<bool>(&mut rt).unwrap());
+}
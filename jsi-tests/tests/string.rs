@@ -0,0 +1,51 @@
+use jsi::{IntoValue, JsiFn, JsiString, RuntimeClone, RuntimeHandle};
+
+mod common;
+
+#[test]
+fn string_utf16() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let s = JsiString::new("a😀é", &mut rt);
+    assert_eq!(4, s.len_utf16(&mut rt));
+    assert_eq!(
+        "a😀é".encode_utf16().collect::<Vec<_>>(),
+        s.to_utf16(&mut rt)
+    );
+
+    // a lone high surrogate, which has no UTF-8 representation
+    let lone = JsiString::from_utf16(&[0x61, 0xd83d], &mut rt);
+    assert_eq!(vec![0x61, 0xd83d], lone.to_utf16(&mut rt));
+
+    let copy = lone.clone(&mut rt);
+    assert_eq!(vec![0x61, 0xd83d], copy.to_utf16(&mut rt));
+    assert!(rt.eq(&lone, &copy));
+
+    let check: JsiFn = rt
+        .evaluate(
+            "(s) => s.length === 2 && s.charCodeAt(1) === 0xd83d",
+            "test.js",
+        )
+        .unwrap()
+        .try_into_js(&mut rt)
+        .unwrap();
+    let out = check
+        .call(std::iter::once(copy.into_value(&mut rt)), &mut rt)
+        .unwrap();
+    assert_eq!(Some(true), out.try_into_js::<bool>(&mut rt));
+}
+
+#[test]
+fn string_len_ignores_globals() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let s = JsiString::new("a😀é", &mut rt);
+    rt.evaluate("globalThis.Object = () => ({ length: 7 })", "test.js")
+        .unwrap();
+    assert_eq!(4, s.len_utf16(&mut rt));
+
+    rt.evaluate("delete globalThis.Object", "test.js").unwrap();
+    assert_eq!(4, s.len_utf16(&mut rt));
+}
@@ -0,0 +1,97 @@
+use jsi::{IntoValue, JsiFn, JsiObject, JsiSymbol, JsiValue, PropName, RuntimeHandle};
+
+mod common;
+
+#[test]
+fn symbol_creation() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let a = JsiSymbol::new(Some("a"), &mut rt).unwrap();
+    let b = JsiSymbol::new(Some("a"), &mut rt).unwrap();
+    assert!(!rt.eq(&a, &b));
+    assert_eq!("Symbol(a)", rt.display(&a).to_string());
+
+    let a = JsiSymbol::for_key("app.key", &mut rt).unwrap();
+    let b = JsiSymbol::for_key("app.key", &mut rt).unwrap();
+    assert!(rt.eq(&a, &b));
+
+    let from_js: JsiSymbol = rt
+        .evaluate("Symbol.for('app.key')", "test.js")
+        .unwrap()
+        .try_into_js(&mut rt)
+        .unwrap();
+    assert!(rt.eq(&a, &from_js));
+
+    let iterator: JsiSymbol = rt
+        .evaluate("Symbol.iterator", "test.js")
+        .unwrap()
+        .try_into_js(&mut rt)
+        .unwrap();
+    let well_known = JsiSymbol::iterator(&mut rt).unwrap();
+    assert!(rt.eq(&iterator, &well_known));
+    assert!(JsiSymbol::well_known("notASymbol", &mut rt).is_none());
+}
+
+#[test]
+fn symbol_keyed_properties() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let mut obj = JsiObject::new(&mut rt);
+
+    let tag = JsiSymbol::to_string_tag(&mut rt).unwrap();
+    let tag = PropName::from_symbol(tag, &mut rt);
+    let value = JsiValue::new_string("Thing", &mut rt);
+    obj.set(tag, &value, &mut rt);
+
+    let private = JsiSymbol::new(Some("private"), &mut rt).unwrap();
+    let private = PropName::from_symbol(private, &mut rt);
+    obj.set(private, &JsiValue::new_number(1.), &mut rt);
+
+    let check: JsiFn = rt
+        .evaluate(
+            "(obj) => Object.prototype.toString.call(obj) === '[object Thing]'
+                && Object.keys(obj).length === 0
+                && Object.getOwnPropertySymbols(obj).length === 2",
+            "test.js",
+        )
+        .unwrap()
+        .try_into_js(&mut rt)
+        .unwrap();
+    let out = check
+        .call(std::iter::once(obj.into_value(&mut rt)), &mut rt)
+        .unwrap();
+    assert_eq!(Some(true), out.try_into_js::<bool>(&mut rt));
+
+    let iterable: JsiObject = rt
+        .evaluate("[1, 2]", "test.js")
+        .unwrap()
+        .try_into_js(&mut rt)
+        .unwrap();
+    let iterator = JsiSymbol::iterator(&mut rt).unwrap();
+    let iterator = iterable.get(PropName::from_symbol(iterator, &mut rt), &mut rt);
+    assert!(iterator.try_into_js::<JsiFn>(&mut rt).is_some());
+}
+
+#[test]
+fn symbol_replaced() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    rt.evaluate("globalThis.Symbol = { for() { return 1 } }", "test.js")
+        .unwrap();
+    assert!(JsiSymbol::new(Some("a"), &mut rt).is_none());
+    assert!(JsiSymbol::for_key("app.key", &mut rt).is_none());
+    assert!(JsiSymbol::iterator(&mut rt).is_none());
+
+    rt.evaluate("delete globalThis.Symbol", "test.js").unwrap();
+    assert!(JsiSymbol::for_key("app.key", &mut rt).is_none());
+    assert!(JsiSymbol::to_primitive(&mut rt).is_none());
+
+    // conversions still work, without Symbol.toPrimitive
+    let value = rt
+        .evaluate("({ valueOf() { return 12 } })", "test.js")
+        .unwrap();
+    assert_eq!(12., value.to_number(&mut rt).unwrap());
+}
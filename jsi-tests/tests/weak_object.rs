@@ -0,0 +1,29 @@
+use jsi::{JsiObject, JsiValue, PropName, RuntimeHandle};
+
+mod common;
+
+#[test]
+fn weak_object_upgrade() {
+    let raw = common::create_raw_runtime();
+    let mut rt = RuntimeHandle::new_unchecked(raw.as_mut_ptr());
+
+    let mut obj = JsiObject::new(&mut rt);
+    obj.set(
+        PropName::new("x", &mut rt),
+        &JsiValue::new_number(1.),
+        &mut rt,
+    );
+    let mut weak = obj.downgrade(&mut rt);
+
+    let upgraded = weak.upgrade(&mut rt).unwrap();
+    let x: Option<f64> = upgraded
+        .get(PropName::new("x", &mut rt), &mut rt)
+        .try_into_js(&mut rt);
+    assert_eq!(Some(1.), x);
+
+    drop(upgraded);
+    drop(obj);
+    rt.collect_garbage("test");
+
+    assert!(weak.upgrade(&mut rt).is_none());
+}
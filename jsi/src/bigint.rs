@@ -0,0 +1,210 @@
+use std::marker::PhantomData;
+
+use crate::integer::checked_integer;
+use crate::{
+    sys, ConversionError, IntoValue, JsiFn, JsiString, JsiValue, PropName, RuntimeClone,
+    RuntimeDisplay, RuntimeEq, RuntimeHandle, TryFromValue,
+};
+
+/// A JavaScript `BigInt`. Used to pass 64-bit (and larger) integers between
+/// Rust and JS without rounding them to the nearest `f64`.
+pub struct JsiBigInt<'rt>(
+    pub(crate) cxx::UniquePtr<sys::JsiBigInt>,
+    pub(crate) PhantomData<&'rt mut ()>,
+);
+
+impl<'rt> JsiBigInt<'rt> {
+    pub fn from_i64(value: i64, rt: &mut RuntimeHandle<'rt>) -> Self {
+        JsiBigInt(
+            sys::BigInt_fromInt64(rt.get_inner_mut(), value),
+            PhantomData,
+        )
+    }
+
+    pub fn from_u64(value: u64, rt: &mut RuntimeHandle<'rt>) -> Self {
+        JsiBigInt(
+            sys::BigInt_fromUint64(rt.get_inner_mut(), value),
+            PhantomData,
+        )
+    }
+
+    /// Values which do not fit in 64 bits are created by passing a string to
+    /// the global `BigInt` function, which is slower. Returns `None` if
+    /// scripts have replaced `globalThis.BigInt` with something that does not
+    /// return a `BigInt`.
+    pub fn from_i128(value: i128, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        if let Ok(value) = i64::try_from(value) {
+            Some(Self::from_i64(value, rt))
+        } else if let Ok(value) = u64::try_from(value) {
+            Some(Self::from_u64(value, rt))
+        } else {
+            Self::from_decimal(&value.to_string(), rt)
+        }
+    }
+
+    /// See [`JsiBigInt::from_i128`].
+    pub fn from_u128(value: u128, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        match u64::try_from(value) {
+            Ok(value) => Some(Self::from_u64(value, rt)),
+            Err(_) => Self::from_decimal(&value.to_string(), rt),
+        }
+    }
+
+    fn from_decimal(digits: &str, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        let ctor: JsiFn = rt
+            .global()
+            .try_get(PropName::new("BigInt", rt), rt)
+            .ok()?
+            .try_into_js(rt)?;
+        let digits = JsiValue::new_string(digits, rt);
+        ctor.call(std::iter::once(digits), rt).ok()?.try_into_js(rt)
+    }
+
+    /// Returns `true` if this value can be converted to an `i64` without
+    /// losing information.
+    pub fn is_i64(&self, rt: &mut RuntimeHandle<'rt>) -> bool {
+        sys::BigInt_isInt64(self.0.as_ref().unwrap(), rt.get_inner_mut())
+    }
+
+    /// Returns `true` if this value can be converted to a `u64` without
+    /// losing information.
+    pub fn is_u64(&self, rt: &mut RuntimeHandle<'rt>) -> bool {
+        sys::BigInt_isUint64(self.0.as_ref().unwrap(), rt.get_inner_mut())
+    }
+
+    /// Returns `None` if the value does not fit in an `i64`.
+    pub fn to_i64(&self, rt: &mut RuntimeHandle<'rt>) -> Option<i64> {
+        if self.is_i64(rt) {
+            Some(sys::BigInt_getInt64(
+                self.0.as_ref().unwrap(),
+                rt.get_inner_mut(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `None` if the value does not fit in a `u64`.
+    pub fn to_u64(&self, rt: &mut RuntimeHandle<'rt>) -> Option<u64> {
+        if self.is_u64(rt) {
+            Some(sys::BigInt_getUint64(
+                self.0.as_ref().unwrap(),
+                rt.get_inner_mut(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `None` if the value does not fit in an `i128`.
+    pub fn to_i128(&self, rt: &mut RuntimeHandle<'rt>) -> Option<i128> {
+        if let Some(value) = self.to_i64(rt) {
+            Some(value.into())
+        } else if let Some(value) = self.to_u64(rt) {
+            Some(value.into())
+        } else {
+            self.to_string_radix(10, rt)?.parse().ok()
+        }
+    }
+
+    /// Returns `None` if the value does not fit in a `u128`.
+    pub fn to_u128(&self, rt: &mut RuntimeHandle<'rt>) -> Option<u128> {
+        match self.to_u64(rt) {
+            Some(value) => Some(value.into()),
+            None => self.to_string_radix(10, rt)?.parse().ok(),
+        }
+    }
+
+    /// Formats the value in the given radix, like `BigInt.prototype.toString`.
+    /// Returns `None` if `radix` is not between 2 and 36.
+    pub fn to_string_radix(&self, radix: u32, rt: &mut RuntimeHandle<'rt>) -> Option<String> {
+        if !(2..=36).contains(&radix) {
+            return None;
+        }
+
+        let s = sys::BigInt_toString(self.0.as_ref().unwrap(), rt.get_inner_mut(), radix as i32)
+            .ok()?;
+        Some(rt.to_string(&JsiString(s, PhantomData)))
+    }
+}
+
+impl RuntimeEq for JsiBigInt<'_> {
+    fn eq(&self, other: &Self, rt: &mut RuntimeHandle<'_>) -> bool {
+        sys::BigInt_compare(
+            rt.get_inner_mut(),
+            self.0.as_ref().unwrap(),
+            other.0.as_ref().unwrap(),
+        )
+    }
+}
+
+impl RuntimeDisplay for JsiBigInt<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, rt: &mut RuntimeHandle<'_>) -> std::fmt::Result {
+        let s = sys::BigInt_toString(self.0.as_ref().unwrap(), rt.get_inner_mut(), 10)
+            .map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", s.to_string(rt.get_inner_mut()))
+    }
+}
+
+impl<'rt> RuntimeClone<'rt> for JsiBigInt<'rt> {
+    fn clone(&self, rt: &mut RuntimeHandle<'rt>) -> Self {
+        let value = sys::Value_copyFromBigInt(rt.get_inner_mut(), self.0.as_ref().unwrap());
+        let value = sys::Value_asBigInt(&value, rt.get_inner_mut())
+            .expect("copy of BigInt is not a BigInt");
+        JsiBigInt(value, PhantomData)
+    }
+}
+
+unsafe impl<'rt> Send for JsiBigInt<'rt> {}
+
+/// An integer which is converted to and from a `BigInt`, instead of a number
+/// like the integer types themselves. Use this for 64-bit values which can be
+/// larger than `Number.MAX_SAFE_INTEGER` (ex. database IDs).
+///
+/// ```no_run
+/// # use jsi::{BigIntValue, IntoValue, RuntimeHandle};
+/// # fn f(rt: &mut RuntimeHandle) {
+/// let id = BigIntValue(9_007_199_254_740_993_u64).into_value(rt);
+/// assert!(id.is_bigint());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BigIntValue<T>(pub T);
+
+macro_rules! impl_bigint_value {
+    ($($ty: ty),*) => {
+        $(
+            /// Only accepts a `BigInt` which is in range.
+            impl<'rt> TryFromValue<'rt> for BigIntValue<$ty> {
+                fn try_from_value(
+                    value: &JsiValue<'rt>,
+                    rt: &mut RuntimeHandle<'rt>,
+                ) -> Result<Self, ConversionError> {
+                    if !value.is_bigint() {
+                        return Err(ConversionError::new("bigint", value, rt));
+                    }
+
+                    checked_integer(value, rt).map(BigIntValue).map_err(|err| {
+                        ConversionError::new("bigint", value, rt).with_reason(err.to_string())
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_bigint_value!(i64, u64, i128, u128);
+
+// 128-bit values can only be converted with `JsiBigInt::from_i128` and
+// `JsiBigInt::from_u128`, which can fail
+impl<'rt> IntoValue<'rt> for BigIntValue<i64> {
+    fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        JsiBigInt::from_i64(self.0, rt).into_value(rt)
+    }
+}
+
+impl<'rt> IntoValue<'rt> for BigIntValue<u64> {
+    fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        JsiBigInt::from_u64(self.0, rt).into_value(rt)
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::sys;
+
+/// An immutable buffer of bytes which can be handed to the runtime, ex. the
+/// source code of a script or Hermes bytecode. The bytes are owned by Rust and
+/// are not copied when the buffer is passed to
+/// [`RuntimeHandle::evaluate_buffer`](crate::RuntimeHandle::evaluate_buffer).
+///
+/// ```no_run
+/// # use jsi::{JsiBuffer, RuntimeHandle};
+/// # fn f(rt: &mut RuntimeHandle) {
+/// // in a real module, this would be `include_bytes!("bootstrap.js")`
+/// static BOOTSTRAP: &[u8] = b"globalThis.ready = true";
+/// let buffer = JsiBuffer::from(BOOTSTRAP);
+/// rt.evaluate_buffer(&buffer, "bootstrap.js").unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct JsiBuffer(pub(crate) cxx::SharedPtr<sys::Buffer>);
+
+impl JsiBuffer {
+    /// Creates a buffer that takes ownership of `bytes`. `bytes.as_ref()` is
+    /// only called once, when the buffer is created.
+    pub fn new<T: AsRef<[u8]> + Send + Sync + 'static>(bytes: T) -> Self {
+        JsiBuffer(sys::Buffer::from_rust(sys::RustBuffer::new(bytes)))
+    }
+
+    /// Creates a buffer by copying a string into a C++ `std::string`.
+    pub fn copy_from_str(s: &str) -> Self {
+        JsiBuffer(sys::Buffer_fromUtf8(s))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        let len = self.len();
+
+        if len == 0 {
+            return &[];
+        }
+
+        unsafe { std::slice::from_raw_parts(self.0.data(), len) }
+    }
+}
+
+impl From<Vec<u8>> for JsiBuffer {
+    fn from(bytes: Vec<u8>) -> Self {
+        JsiBuffer::new(bytes)
+    }
+}
+
+impl From<String> for JsiBuffer {
+    fn from(s: String) -> Self {
+        JsiBuffer::new(s)
+    }
+}
+
+impl From<&'static [u8]> for JsiBuffer {
+    fn from(bytes: &'static [u8]) -> Self {
+        JsiBuffer::new(bytes)
+    }
+}
+
+impl From<&'static str> for JsiBuffer {
+    fn from(s: &'static str) -> Self {
+        JsiBuffer::new(s)
+    }
+}
+
+impl From<Arc<[u8]>> for JsiBuffer {
+    fn from(bytes: Arc<[u8]>) -> Self {
+        JsiBuffer::new(bytes)
+    }
+}
+
+// the contents of a buffer are never modified after it is created
+unsafe impl Send for JsiBuffer {}
+unsafe impl Sync for JsiBuffer {}
@@ -0,0 +1,243 @@
+use crate::{
+    AsValue, FromObject, FromValue, JsiArray, JsiArrayBuffer, JsiBigInt, JsiFn, JsiObject,
+    JsiString, JsiSymbol, JsiValue, PropName, RuntimeHandle, SharedJsiHostObject,
+    SharedJsiUserHostObject, TryFromValue,
+};
+
+/// A finer classification of a value than [`JsiValueKind`](crate::JsiValueKind),
+/// which also tells apart the built-in kinds of objects. Each variant holds
+/// the value, already converted to the most specific handle available.
+///
+/// ```no_run
+/// # use jsi::{JsiValue, JsiValueClass, RuntimeHandle};
+/// # fn f<'rt>(value: JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> anyhow::Result<()> {
+/// match value.classify(rt) {
+///     JsiValueClass::String(s) => println!("got {}", rt.to_string(&s)),
+///     JsiValueClass::Function(f) => {
+///         f.call(std::iter::empty(), rt)?;
+///     }
+///     JsiValueClass::Array(arr) => println!("got {} items", arr.len(rt)),
+///     _ => anyhow::bail!("expected a string, a function or an array"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub enum JsiValueClass<'rt> {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(JsiString<'rt>),
+    Symbol(JsiSymbol<'rt>),
+    BigInt(JsiBigInt<'rt>),
+    Array(JsiArray<'rt>),
+    ArrayBuffer(JsiArrayBuffer<'rt>),
+    /// A typed array (ex. `Uint8Array`), but not a `DataView`.
+    TypedArray(JsiObject<'rt>),
+    Function(JsiFn<'rt>),
+    /// A host object implemented in Rust with
+    /// [`UserHostObject`](crate::UserHostObject).
+    UserHostObject(SharedJsiUserHostObject<'rt>),
+    /// A host object implemented in some other language.
+    HostObject(SharedJsiHostObject<'rt>),
+    Promise(JsiObject<'rt>),
+    Date(JsiObject<'rt>),
+    Map(JsiObject<'rt>),
+    Set(JsiObject<'rt>),
+    /// An instance of `Error` or one of its subclasses.
+    Error(JsiObject<'rt>),
+    /// Any other object.
+    Object(JsiObject<'rt>),
+}
+
+impl<'rt> JsiValue<'rt> {
+    pub fn classify(&self, rt: &mut RuntimeHandle<'rt>) -> JsiValueClass<'rt> {
+        if self.is_undefined() {
+            JsiValueClass::Undefined
+        } else if self.is_null() {
+            JsiValueClass::Null
+        } else if self.is_bool() {
+            JsiValueClass::Bool(FromValue::from_value(self, rt).unwrap())
+        } else if self.is_number() {
+            JsiValueClass::Number(FromValue::from_value(self, rt).unwrap())
+        } else if self.is_string() {
+            JsiValueClass::String(FromValue::from_value(self, rt).unwrap())
+        } else if self.is_symbol() {
+            JsiValueClass::Symbol(FromValue::from_value(self, rt).unwrap())
+        } else if self.is_bigint() {
+            JsiValueClass::BigInt(FromValue::from_value(self, rt).unwrap())
+        } else {
+            let obj: JsiObject = FromValue::from_value(self, rt).unwrap();
+            obj.classify(rt)
+        }
+    }
+}
+
+impl<'rt> JsiObject<'rt> {
+    /// See [`JsiValue::classify`]. Dates, maps and sets are brand-checked by
+    /// calling a method of the built-in prototype on the object, so they are
+    /// recognized even if they come from another realm or their prototype
+    /// has been changed. Errors are brand-checked with `Error.isError` where
+    /// the runtime has it. Promises, and errors in runtimes without
+    /// `Error.isError`, are detected by looking for the prototype of the
+    /// built-in constructor in the object's prototype chain.
+    ///
+    /// The built-ins are looked up on the global object every time, so this
+    /// relies on scripts not replacing them: after
+    /// `Map.prototype.has = () => true`, every plain object is classified as
+    /// a [`JsiValueClass::Map`], and the replaced function is called. Globals
+    /// that are missing or throw when they are read are skipped.
+    pub fn classify(mut self, rt: &mut RuntimeHandle<'rt>) -> JsiValueClass<'rt> {
+        if self.is_array(rt) {
+            return JsiValueClass::Array(FromObject::from_object(&self, rt).unwrap());
+        }
+
+        if self.is_array_buffer(rt) {
+            return JsiValueClass::ArrayBuffer(FromObject::from_object(&self, rt).unwrap());
+        }
+
+        if self.is_fn(rt) {
+            return JsiValueClass::Function(FromObject::from_object(&self, rt).unwrap());
+        }
+
+        if self.is_host_object(rt) {
+            return match FromObject::from_object(&self, rt) {
+                Some(user) => JsiValueClass::UserHostObject(user),
+                None => JsiValueClass::HostObject(FromObject::from_object(&self, rt).unwrap()),
+            };
+        }
+
+        if is_typed_array(&mut self, rt) {
+            return JsiValueClass::TypedArray(self);
+        }
+
+        let brands: [Brand<'rt>; 3] = [
+            ("Date", "getTime", JsiValueClass::Date),
+            ("Map", "has", JsiValueClass::Map),
+            ("Set", "has", JsiValueClass::Set),
+        ];
+
+        for (ctor, method, class) in brands {
+            if has_brand(&self, ctor, method, rt) {
+                return class(self);
+            }
+        }
+
+        if is_error(&self, rt) {
+            return JsiValueClass::Error(self);
+        }
+
+        // there is no way to brand-check a promise without calling `then`,
+        // which can run user code
+        if inherits_from(&self, "Promise", rt) {
+            return JsiValueClass::Promise(self);
+        }
+
+        JsiValueClass::Object(self)
+    }
+}
+
+/// The name of a built-in constructor, a method of its prototype which
+/// brand-checks `this`, and the class of the objects that pass.
+type Brand<'rt> = (
+    &'static str,
+    &'static str,
+    fn(JsiObject<'rt>) -> JsiValueClass<'rt>,
+);
+
+/// `globalThis[name]`, or `None` if it is missing or its getter throws.
+fn global<'rt, T: TryFromValue<'rt>>(name: &str, rt: &mut RuntimeHandle<'rt>) -> Option<T> {
+    rt.global()
+        .try_get(PropName::new(name, rt), rt)
+        .ok()?
+        .try_into_js(rt)
+}
+
+/// `obj[name]`, or `None` if it is missing or its getter throws.
+fn member<'rt, T: TryFromValue<'rt>>(
+    obj: &JsiObject<'rt>,
+    name: &str,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Option<T> {
+    obj.try_get(PropName::new(name, rt), rt)
+        .ok()?
+        .try_into_js(rt)
+}
+
+/// Calls `globalThis[ctor].prototype[method]` with `obj` as `this`, which
+/// throws a `TypeError` unless `obj` has the internal slots of `ctor`.
+fn has_brand<'rt>(
+    obj: &JsiObject<'rt>,
+    ctor: &str,
+    method: &str,
+    rt: &mut RuntimeHandle<'rt>,
+) -> bool {
+    let method: Option<JsiFn> = global::<JsiObject>(ctor, rt)
+        .and_then(|ctor| member::<JsiObject>(&ctor, "prototype", rt))
+        .and_then(|proto| member(&proto, method, rt));
+
+    match method {
+        Some(method) => method.call_with_this(obj, std::iter::empty(), rt).is_ok(),
+        None => false,
+    }
+}
+
+/// Whether `globalThis[ctor].prototype` is in the prototype chain of `obj`.
+/// Unlike `instanceof`, this does not call `Symbol.hasInstance`.
+fn inherits_from<'rt>(obj: &JsiObject<'rt>, ctor: &str, rt: &mut RuntimeHandle<'rt>) -> bool {
+    let target: Option<JsiObject> =
+        global::<JsiObject>(ctor, rt).and_then(|ctor| member(&ctor, "prototype", rt));
+    let target = match target {
+        Some(target) => target,
+        None => return false,
+    };
+
+    let target = target.as_value(rt);
+    let mut current = obj.get_prototype(rt);
+    while let Ok(Some(proto)) = current {
+        if proto.as_value(rt).strict_equals(&target, rt) {
+            return true;
+        }
+        current = proto.get_prototype(rt);
+    }
+
+    false
+}
+
+/// `Error.isError(obj)` if the runtime has it, otherwise checks whether
+/// `obj` inherits from `Error.prototype`.
+fn is_error<'rt>(obj: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> bool {
+    let is_error: Option<JsiFn> =
+        global::<JsiObject>("Error", rt).and_then(|error| member(&error, "isError", rt));
+
+    match is_error {
+        Some(is_error) => {
+            let arg = obj.as_value(rt);
+            is_error
+                .call(std::iter::once(arg), rt)
+                .map(|res| res.is_truthy(rt))
+                .unwrap_or(false)
+        }
+        None => inherits_from(obj, "Error", rt),
+    }
+}
+
+/// `ArrayBuffer.isView(obj)`, excluding objects that inherit from
+/// `DataView.prototype`
+fn is_typed_array<'rt>(obj: &mut JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> bool {
+    let is_view: Option<JsiFn> = global::<JsiObject>("ArrayBuffer", rt)
+        .and_then(|array_buffer| member(&array_buffer, "isView", rt));
+
+    let is_view = match is_view {
+        Some(is_view) => is_view,
+        None => return false,
+    };
+
+    let arg = obj.as_value(rt);
+    let is_view = is_view
+        .call(std::iter::once(arg), rt)
+        .map(|res| res.is_truthy(rt))
+        .unwrap_or(false);
+
+    is_view && !inherits_from(obj, "DataView", rt)
+}
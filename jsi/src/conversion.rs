@@ -0,0 +1,172 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use crate::{sys, HostError, JsiValue, RuntimeHandle};
+
+/// Fallible conversion from [`JsiValue`]. Unlike [`FromValue`](crate::FromValue),
+/// which is implemented for every type that implements this trait, the error
+/// says what was expected, what was received, and where in the value the
+/// conversion failed.
+pub trait TryFromValue<'rt>: Sized {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError>;
+}
+
+/// One step in the path to a value that could not be converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A property name, ex. `items` in `args[1].items`.
+    Key(String),
+    /// An array index, ex. `1` in `args[1].items`.
+    Index(usize),
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathSegment::Key(key) if is_identifier(key) => write!(f, ".{}", key),
+            PathSegment::Key(key) => write!(f, "[{:?}]", key),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' || c == '$' => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Formats a path like a JS property access, ex. `args[1].items[3].price`.
+pub fn format_path(path: &[PathSegment]) -> String {
+    let mut out = String::new();
+
+    for (i, segment) in path.iter().enumerate() {
+        match segment {
+            PathSegment::Key(key) if i == 0 && is_identifier(key) => out.push_str(key),
+            segment => out.push_str(&segment.to_string()),
+        }
+    }
+
+    out
+}
+
+/// A JS value could not be converted to a Rust type.
+///
+/// Conversions of nested values (ex. the items of an array) add their
+/// position to the path of the error as it is returned, so the error points
+/// at the innermost value that failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionError {
+    path: Vec<PathSegment>,
+    expected: Cow<'static, str>,
+    received: &'static str,
+    reason: Option<String>,
+}
+
+impl ConversionError {
+    /// Creates an error for a value which is not of the `expected` type. The
+    /// received type is taken from `value`.
+    pub fn new<'rt>(
+        expected: impl Into<Cow<'static, str>>,
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Self {
+        ConversionError {
+            path: Vec::new(),
+            expected: expected.into(),
+            received: received_type(value, rt),
+            reason: None,
+        }
+    }
+
+    /// Replaces the default message (`expected X, got Y`), for values which
+    /// have the right type but are still invalid (ex. a number which is out
+    /// of range).
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Adds a segment to the start of the path.
+    pub fn at(mut self, segment: PathSegment) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+
+    /// Adds a property name to the start of the path.
+    pub fn at_key(self, key: impl Into<String>) -> Self {
+        self.at(PathSegment::Key(key.into()))
+    }
+
+    /// Adds an array index to the start of the path.
+    pub fn at_index(self, index: usize) -> Self {
+        self.at(PathSegment::Index(index))
+    }
+
+    /// The path to the value that could not be converted, outermost first.
+    /// Empty if the value itself could not be converted.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// The name of the type that was expected, ex. `string` or `u8`.
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// The type of the value that was received, like `typeof` except that
+    /// `null` is `"null"`.
+    pub fn received(&self) -> &'static str {
+        self.received
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", format_path(&self.path))?;
+        }
+
+        match &self.reason {
+            Some(reason) => write!(f, "{}", reason),
+            None => write!(f, "expected {}, got {}", self.expected, self.received),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl From<ConversionError> for HostError {
+    /// Conversion errors are thrown as a `TypeError` with `path`, `expected`
+    /// and `received` properties.
+    fn from(err: ConversionError) -> Self {
+        let mut host = HostError::type_error(err.to_string())
+            .with_property("expected", err.expected.into_owned())
+            .with_property("received", err.received);
+
+        if !err.path.is_empty() {
+            host = host.with_property("path", format_path(&err.path));
+        }
+
+        host
+    }
+}
+
+fn received_type<'rt>(value: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> &'static str {
+    match sys::Value_asObject(&*value.0, rt.get_inner_mut()) {
+        Ok(obj) if obj.is_function(rt.get_inner_mut()) => "function",
+        _ => value.type_name(),
+    }
+}
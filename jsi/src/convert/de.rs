@@ -1,7 +1,7 @@
 use anyhow::Context;
 use jsi::{
-    IntoValue, JsiArray, JsiArrayBuffer, JsiFn, JsiObject, JsiString, JsiValue, JsiValueKind,
-    PropName, RuntimeClone, RuntimeHandle,
+    format_path, IntoValue, JsiArray, JsiArrayBuffer, JsiBigInt, JsiFn, JsiObject, JsiString,
+    JsiValue, JsiValueKind, PathSegment, PropName, RuntimeClone, RuntimeHandle,
 };
 use serde::{
     de::{IntoDeserializer, SeqAccess},
@@ -11,12 +11,60 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum JsiDeserializeError {
+    #[error(transparent)]
+    Js(#[from] jsi::DetachedJsError),
+    /// No longer produced; exceptions thrown from JavaScript are reported as
+    /// [`JsiDeserializeError::Js`].
+    #[deprecated(note = "exceptions are reported as `JsiDeserializeError::Js`")]
     #[error(transparent)]
     Native(#[from] cxx::Exception),
+    /// The value had the wrong type. Includes the path to the value.
+    #[error(transparent)]
+    Conversion(#[from] jsi::ConversionError),
+    /// Any other error while deserializing a nested value.
+    #[error("{}: {source}", format_path(path))]
+    At {
+        path: Vec<PathSegment>,
+        source: Box<JsiDeserializeError>,
+    },
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+impl From<jsi::JsError<'_>> for JsiDeserializeError {
+    fn from(err: jsi::JsError) -> Self {
+        Self::Js(err.detach())
+    }
+}
+
+impl JsiDeserializeError {
+    /// The path to the value that could not be deserialized, outermost first,
+    /// ex. `[Key("items"), Index(3), Key("price")]`.
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            JsiDeserializeError::Conversion(err) => err.path(),
+            JsiDeserializeError::At { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    fn at(self, segment: PathSegment) -> Self {
+        match self {
+            JsiDeserializeError::Conversion(err) => {
+                JsiDeserializeError::Conversion(err.at(segment))
+            }
+            JsiDeserializeError::At { mut path, source } => {
+                path.insert(0, segment);
+                JsiDeserializeError::At { path, source }
+            }
+            err => JsiDeserializeError::At {
+                path: vec![segment],
+                source: Box::new(err),
+            },
+        }
+    }
+}
+
 impl serde::de::Error for JsiDeserializeError {
     fn custom<T>(msg: T) -> Self
     where
@@ -68,6 +116,26 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
             return self.deserialize_map(visitor);
         }
 
+        if self.value.is_bigint() {
+            let rt = self.rt;
+            let val: JsiBigInt = self
+                .value
+                .try_into_js(rt)
+                .context("value was not a BigInt")?;
+
+            return if let Some(val) = val.to_i64(rt) {
+                visitor.visit_i64(val)
+            } else if let Some(val) = val.to_u64(rt) {
+                visitor.visit_u64(val)
+            } else if let Some(val) = val.to_i128(rt) {
+                visitor.visit_i128(val)
+            } else if let Some(val) = val.to_u128(rt) {
+                visitor.visit_u128(val)
+            } else {
+                Err(anyhow::anyhow!("BigInt does not fit in 128 bits").into())
+            };
+        }
+
         if self.value.is_symbol() {
             return Err(anyhow::anyhow!("Symbols cannot be transferred to native code yet").into());
         }
@@ -82,10 +150,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_bool: {}", self.value);
 
         let rt = self.rt;
-        let val: bool = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a boolean")?;
+        let val: bool = self.value.convert(rt)?;
         visitor.visit_bool(val)
     }
 
@@ -95,12 +160,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_i8: {}", self.value);
 
-        let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
-        visitor.visit_i8(val as i8)
+        visitor.visit_i8(self.value.convert(self.rt)?)
     }
 
     fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -109,12 +169,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_i16: {}", self.value);
 
-        let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
-        visitor.visit_i16(val as i16)
+        visitor.visit_i16(self.value.convert(self.rt)?)
     }
 
     fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -123,12 +178,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_i32: {}", self.value);
 
-        let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
-        visitor.visit_i32(val as i32)
+        visitor.visit_i32(self.value.convert(self.rt)?)
     }
 
     fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -137,12 +187,16 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_i64: {}", self.value);
 
-        let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
-        visitor.visit_i64(val as i64)
+        visitor.visit_i64(self.value.convert(self.rt)?)
+    }
+
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        // trace!("deserialize_i128: {}", self.value);
+
+        visitor.visit_i128(self.value.convert(self.rt)?)
     }
 
     fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -151,12 +205,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_u8: {}", self.value);
 
-        let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
-        visitor.visit_u8(val as u8)
+        visitor.visit_u8(self.value.convert(self.rt)?)
     }
 
     fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -165,12 +214,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_u16: {}", self.value);
 
-        let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
-        visitor.visit_u16(val as u16)
+        visitor.visit_u16(self.value.convert(self.rt)?)
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -179,12 +223,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_u32: {}", self.value);
 
-        let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
-        visitor.visit_u32(val as u32)
+        visitor.visit_u32(self.value.convert(self.rt)?)
     }
 
     fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -193,12 +232,16 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_u64: {}", self.value);
 
-        let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
-        visitor.visit_u64(val as u64)
+        visitor.visit_u64(self.value.convert(self.rt)?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        // trace!("deserialize_u128: {}", self.value);
+
+        visitor.visit_u128(self.value.convert(self.rt)?)
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -208,10 +251,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_f32: {}", self.value);
 
         let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
+        let val: f64 = self.value.convert(rt)?;
         visitor.visit_f32(val as f32)
     }
 
@@ -222,10 +262,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_f64: {}", self.value);
 
         let rt = self.rt;
-        let val: f64 = self
-            .value
-            .try_into_js(rt)
-            .context("value was not a number")?;
+        let val: f64 = self.value.convert(rt)?;
         visitor.visit_f64(val)
     }
 
@@ -236,10 +273,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_char: {}", self.value);
 
         let rt = self.rt;
-        let val: JsiString = self
-            .value
-            .try_into_js(rt)
-            .context("the value is not a string")?;
+        let val: JsiString = self.value.convert(rt)?;
         let val = rt.to_string(&val);
         visitor.visit_char(val.chars().next().unwrap())
     }
@@ -251,10 +285,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_str: {}", self.value);
 
         let rt = self.rt;
-        let val: JsiString = self
-            .value
-            .try_into_js(rt)
-            .context("the value is not a string")?;
+        let val: JsiString = self.value.convert(rt)?;
         let val = rt.to_string(&val);
         visitor.visit_str(val.as_str())
     }
@@ -266,10 +297,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_string: {}", self.value);
 
         let rt = self.rt;
-        let val: JsiString = self
-            .value
-            .try_into_js(rt)
-            .context("the value is not a string")?;
+        let val: JsiString = self.value.convert(rt)?;
         let val = rt.to_string(&val);
         visitor.visit_string(val)
     }
@@ -280,10 +308,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
     {
         // trace!("deserialize_bytes: {}", self.value);
         let rt = self.rt;
-        let value: JsiArrayBuffer = self
-            .value
-            .try_into_js(rt)
-            .context("the value is not an array buffer")?;
+        let value: JsiArrayBuffer = self.value.convert(rt)?;
         visitor.visit_bytes(value.data(rt))
     }
 
@@ -295,10 +320,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
 
         let rt = self.rt;
 
-        let value: JsiArrayBuffer = self
-            .value
-            .try_into_js(rt)
-            .context("the value is not an array buffer")?;
+        let value: JsiArrayBuffer = self.value.convert(rt)?;
 
         visitor.visit_byte_buf(Vec::from(value.data(rt)))
     }
@@ -323,7 +345,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_unit: {}", self.value);
 
         let rt = self.rt;
-        let mut value: JsiObject = self
+        let value: JsiObject = self
             .value
             .try_into_js(rt)
             .context("expected empty object for unit")?;
@@ -368,10 +390,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_seq: {}", self.value);
 
         let rt = self.rt;
-        let obj: JsiObject = self
-            .value
-            .try_into_js(rt)
-            .context("value is not an object; cannot deserialize as seq")?;
+        let obj: JsiObject = self.value.convert(rt)?;
 
         let iterator: JsiObject = if obj.is_array(rt) {
             let values: JsiFn = obj
@@ -396,6 +415,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
             rt,
             next,
             this: iterator,
+            idx: 0,
         })
     }
 
@@ -407,10 +427,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
 
         let rt = self.rt;
 
-        let obj: JsiObject = self
-            .value
-            .try_into_js(rt)
-            .context("value is not an object; cannot deserialize as tuple")?;
+        let obj: JsiObject = self.value.convert(rt)?;
 
         visitor.visit_seq(JsiTupleVisitor {
             rt,
@@ -441,10 +458,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         // trace!("deserialize_map: {}", self.value);
 
         let rt = self.rt;
-        let mut obj: JsiObject = self
-            .value
-            .try_into_js(rt)
-            .context("value is not an object; cannot deserialize as map/struct")?;
+        let mut obj: JsiObject = self.value.convert(rt)?;
 
         let map_ctor = rt.global().get(PropName::new("Map", rt), rt);
 
@@ -508,6 +522,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
         visitor.visit_map(JsiDeserializerMapAccess {
             rt,
             value: None,
+            key: None,
             next,
             this: iterator,
         })
@@ -548,7 +563,7 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
                 let s = self.rt.to_string(&s);
                 visitor.visit_enum(s.into_deserializer())
             }
-            JsiValueKind::Object(mut obj) => {
+            JsiValueKind::Object(obj) => {
                 let props = obj.properties(self.rt);
 
                 if props.len(self.rt) != 1 {
@@ -566,10 +581,13 @@ impl<'a, 'rt: 'a, 'de> Deserializer<'de> for JsiDeserializer<'a, 'rt> {
 
                 let value = obj.get(prop, self.rt);
 
+                let key = self.rt.to_string(&variant);
+
                 visitor.visit_enum(JsiDeserializerEnumAccess {
                     variant: variant.into_value(self.rt),
                     rt: self.rt,
                     value,
+                    key,
                 })
             }
             _ => Err(anyhow::anyhow!("invalid type to serialize into enum").into()),
@@ -612,11 +630,14 @@ impl<'a, 'rt: 'a, 'de> SeqAccess<'de> for JsiTupleVisitor<'a, 'rt> {
         let rt = &mut self.rt;
 
         if self.idx < self.len {
-            let prop = PropName::new(self.idx.to_string().as_str(), rt);
+            let idx = self.idx;
+            let prop = PropName::new(idx.to_string().as_str(), rt);
             let value = self.obj.get(prop, rt);
             self.idx += 1;
 
-            let value = seed.deserialize(JsiDeserializer { rt: self.rt, value })?;
+            let value = seed
+                .deserialize(JsiDeserializer { rt: self.rt, value })
+                .map_err(|err| err.at(PathSegment::Index(idx)))?;
             Ok(Some(value))
         } else {
             Ok(None)
@@ -628,6 +649,7 @@ pub struct JsiDeserializerEnumAccess<'a, 'rt: 'a> {
     rt: &'a mut RuntimeHandle<'rt>,
     variant: JsiValue<'rt>,
     value: JsiValue<'rt>,
+    key: String,
 }
 
 impl<'a, 'rt: 'a, 'de> serde::de::EnumAccess<'de> for JsiDeserializerEnumAccess<'a, 'rt> {
@@ -652,6 +674,7 @@ impl<'a, 'rt: 'a, 'de> serde::de::EnumAccess<'de> for JsiDeserializerEnumAccess<
                 JsiDeserializerValueAccess {
                     rt,
                     value: self.value,
+                    key: self.key,
                 },
             )
         })
@@ -661,6 +684,9 @@ impl<'a, 'rt: 'a, 'de> serde::de::EnumAccess<'de> for JsiDeserializerEnumAccess<
 pub struct JsiDeserializerValueAccess<'a, 'rt: 'a> {
     rt: &'a mut RuntimeHandle<'rt>,
     value: JsiValue<'rt>,
+
+    /// the name of the variant, which is the key of `value`
+    key: String,
 }
 
 impl<'a, 'rt: 'a, 'de> serde::de::VariantAccess<'de> for JsiDeserializerValueAccess<'a, 'rt> {
@@ -668,7 +694,7 @@ impl<'a, 'rt: 'a, 'de> serde::de::VariantAccess<'de> for JsiDeserializerValueAcc
 
     fn unit_variant(self) -> Result<(), Self::Error> {
         let rt = self.rt;
-        let mut value: JsiObject = self
+        let value: JsiObject = self
             .value
             .try_into_js(rt)
             .context("expected empty object for unit")?;
@@ -688,6 +714,7 @@ impl<'a, 'rt: 'a, 'de> serde::de::VariantAccess<'de> for JsiDeserializerValueAcc
             rt: self.rt,
             value: self.value,
         })
+        .map_err(|err| err.at(PathSegment::Key(self.key)))
     }
 
     fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
@@ -700,6 +727,7 @@ impl<'a, 'rt: 'a, 'de> serde::de::VariantAccess<'de> for JsiDeserializerValueAcc
         };
 
         de.deserialize_tuple(len, visitor)
+            .map_err(|err| err.at(PathSegment::Key(self.key)))
     }
 
     fn struct_variant<V>(
@@ -716,6 +744,7 @@ impl<'a, 'rt: 'a, 'de> serde::de::VariantAccess<'de> for JsiDeserializerValueAcc
         };
 
         de.deserialize_struct("", fields, visitor)
+            .map_err(|err| err.at(PathSegment::Key(self.key)))
     }
 }
 
@@ -727,6 +756,9 @@ pub struct JsiDeserializerSeqAccess<'a, 'rt: 'a> {
 
     /// `next()` function of JS `Iterator`
     next: JsiFn<'rt>,
+
+    /// index of the next item, for error messages
+    idx: usize,
 }
 
 impl<'a, 'rt: 'a, 'de> serde::de::SeqAccess<'de> for JsiDeserializerSeqAccess<'a, 'rt> {
@@ -749,7 +781,10 @@ impl<'a, 'rt: 'a, 'de> serde::de::SeqAccess<'de> for JsiDeserializerSeqAccess<'a
         if !done.is_truthy(rt) {
             let item = next.get(PropName::new("value", rt), rt);
 
-            let item = seed.deserialize(JsiDeserializer { rt, value: item })?;
+            let item = seed
+                .deserialize(JsiDeserializer { rt, value: item })
+                .map_err(|err| err.at(PathSegment::Index(self.idx)))?;
+            self.idx += 1;
 
             Ok(Some(item))
         } else {
@@ -768,6 +803,9 @@ pub struct JsiDeserializerMapAccess<'a, 'rt: 'a> {
     next: JsiFn<'rt>,
 
     value: Option<JsiValue<'rt>>,
+
+    /// the key of `value`, for error messages
+    key: Option<String>,
 }
 
 impl<'a, 'rt: 'a, 'de> serde::de::MapAccess<'de> for JsiDeserializerMapAccess<'a, 'rt> {
@@ -802,6 +840,7 @@ impl<'a, 'rt: 'a, 'de> serde::de::MapAccess<'de> for JsiDeserializerMapAccess<'a
             let value = entry.get(1, rt);
 
             self.value = Some(value);
+            self.key = Some(rt.display(&key).to_string());
 
             let key = seed.deserialize(JsiDeserializer { rt: rt, value: key })?;
 
@@ -816,7 +855,11 @@ impl<'a, 'rt: 'a, 'de> serde::de::MapAccess<'de> for JsiDeserializerMapAccess<'a
         V: serde::de::DeserializeSeed<'de>,
     {
         match self.value.take() {
-            Some(value) => seed.deserialize(JsiDeserializer { rt: self.rt, value }),
+            Some(value) => {
+                let key = self.key.take().unwrap_or_default();
+                seed.deserialize(JsiDeserializer { rt: self.rt, value })
+                    .map_err(|err| err.at(PathSegment::Key(key)))
+            }
             None => Err(anyhow::anyhow!("missing value").into()),
         }
     }
@@ -1,4 +1,5 @@
 use jsi::*;
+
 use serde::{
     ser::{
         SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
@@ -11,11 +12,64 @@ use serde::{
 /// structures and objects from `serde_json` into JavaScript.
 pub struct JsiSerializer<'a, 'rt: 'a> {
     rt: &'a mut RuntimeHandle<'rt>,
+    options: Options,
+}
+
+/// How 64-bit and 128-bit integers are serialized, passed down to the
+/// serializers of nested values.
+#[derive(Debug, Clone, Copy, Default)]
+struct Options {
+    bigints: bool,
+    safe_integers: bool,
 }
 
 impl<'a, 'rt: 'a> JsiSerializer<'a, 'rt> {
     pub fn new(rt: &'a mut RuntimeHandle<'rt>) -> Self {
-        Self { rt }
+        Self {
+            rt,
+            options: Options::default(),
+        }
+    }
+
+    /// Serializes 64-bit and 128-bit integers as `BigInt`s instead of
+    /// numbers, so that values above 2^53 (ex. database IDs) are not rounded.
+    /// This is off by default because JS code can't mix `BigInt`s and
+    /// numbers in arithmetic.
+    pub fn with_bigints(mut self, bigints: bool) -> Self {
+        self.options.bigints = bigints;
+        self
+    }
+
+    /// Fails to serialize 64-bit and 128-bit integers outside of
+    /// `Number.MIN_SAFE_INTEGER..=Number.MAX_SAFE_INTEGER` instead of
+    /// rounding them to the nearest number. Has no effect if
+    /// [`JsiSerializer::with_bigints`] is set, since `BigInt`s are exact.
+    pub fn with_safe_integers(mut self, safe_integers: bool) -> Self {
+        self.options.safe_integers = safe_integers;
+        self
+    }
+
+    fn big_integer<T: TryInto<i128> + std::fmt::Display + Copy>(
+        self,
+        n: T,
+        as_f64: f64,
+        big: impl FnOnce(&mut RuntimeHandle<'rt>) -> Option<JsiBigInt<'rt>>,
+    ) -> Result<JsiValue<'rt>, JsiSerializeError> {
+        if self.options.bigints {
+            return match big(self.rt) {
+                Some(big) => Ok(big.into_value(self.rt)),
+                None => Err(JsiSerializeError::Custom(format!(
+                    "could not create a BigInt for {}, is globalThis.BigInt replaced?",
+                    n
+                ))),
+            };
+        }
+
+        if !self.options.safe_integers {
+            return Ok(JsiValue::new_number(as_f64));
+        }
+
+        JsiValue::new_safe_integer(n).map_err(|err| JsiSerializeError::Custom(err.to_string()))
     }
 }
 
@@ -52,7 +106,11 @@ impl<'a, 'rt: 'a> Serializer for JsiSerializer<'a, 'rt> {
     }
 
     fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
-        Ok(JsiValue::new_number(v as f64))
+        self.big_integer(v, v as f64, |rt| Some(JsiBigInt::from_i64(v, rt)))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.big_integer(v, v as f64, |rt| JsiBigInt::from_i128(v, rt))
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
@@ -68,7 +126,11 @@ impl<'a, 'rt: 'a> Serializer for JsiSerializer<'a, 'rt> {
     }
 
     fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
-        Ok(JsiValue::new_number(v as f64))
+        self.big_integer(v, v as f64, |rt| Some(JsiBigInt::from_u64(v, rt)))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        self.big_integer(v, v as f64, |rt| JsiBigInt::from_u128(v, rt))
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
@@ -94,8 +156,7 @@ impl<'a, 'rt: 'a> Serializer for JsiSerializer<'a, 'rt> {
             .try_into_js(rt)
             .expect("ArrayBuffer constructor is not a function");
         let array_buffer = array_buffer_ctor
-            .call_as_constructor(vec![JsiValue::new_number(v.len() as f64)], rt)
-            .expect("ArrayBuffer constructor threw an exception");
+            .call_as_constructor(vec![JsiValue::new_number(v.len() as f64)], rt)?;
         let array_buffer: JsiArrayBuffer = array_buffer
             .try_into_js(rt)
             .expect("ArrayBuffer constructor did not return an ArrayBuffer");
@@ -159,13 +220,19 @@ impl<'a, 'rt: 'a> Serializer for JsiSerializer<'a, 'rt> {
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
         match len {
-            Some(len) => Ok(JsiSeqSerializer::new(len, self.rt)),
+            Some(len) => Ok(JsiSeqSerializer {
+                options: self.options,
+                ..JsiSeqSerializer::new(len, self.rt)
+            }),
             None => Err(JsiSerializeError::UnsizedSequence),
         }
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Ok(JsiTupleSerializer::new(self.rt))
+        Ok(JsiTupleSerializer {
+            options: self.options,
+            ..JsiTupleSerializer::new(self.rt)
+        })
     }
 
     fn serialize_tuple_struct(
@@ -173,7 +240,9 @@ impl<'a, 'rt: 'a> Serializer for JsiSerializer<'a, 'rt> {
         name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        Ok(JsiTupleVariantSerializer::new(name, self.rt))
+        let mut ser = JsiTupleVariantSerializer::new(name, self.rt);
+        ser.inner.options = self.options;
+        Ok(ser)
     }
 
     fn serialize_tuple_variant(
@@ -183,11 +252,16 @@ impl<'a, 'rt: 'a> Serializer for JsiSerializer<'a, 'rt> {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        Ok(JsiTupleVariantSerializer::new(variant, self.rt))
+        let mut ser = JsiTupleVariantSerializer::new(variant, self.rt);
+        ser.inner.options = self.options;
+        Ok(ser)
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Ok(JsiMapSerializer::new(self.rt))
+        Ok(JsiMapSerializer {
+            options: self.options,
+            ..JsiMapSerializer::new(self.rt)
+        })
     }
 
     fn serialize_struct(
@@ -195,7 +269,10 @@ impl<'a, 'rt: 'a> Serializer for JsiSerializer<'a, 'rt> {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(JsiStructSerializer::new(self.rt))
+        Ok(JsiStructSerializer {
+            options: self.options,
+            ..JsiStructSerializer::new(self.rt)
+        })
     }
 
     fn serialize_struct_variant(
@@ -205,7 +282,9 @@ impl<'a, 'rt: 'a> Serializer for JsiSerializer<'a, 'rt> {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Ok(JsiStructVariantSerializer::new(variant, self.rt))
+        let mut ser = JsiStructVariantSerializer::new(variant, self.rt);
+        ser.inner.options = self.options;
+        Ok(ser)
     }
 }
 
@@ -213,12 +292,18 @@ pub struct JsiSeqSerializer<'a, 'rt: 'a> {
     rt: &'a mut RuntimeHandle<'rt>,
     arr: JsiArray<'rt>,
     idx: usize,
+    options: Options,
 }
 
 impl<'a, 'rt: 'a> JsiSeqSerializer<'a, 'rt> {
     pub fn new(len: usize, rt: &'a mut RuntimeHandle<'rt>) -> Self {
         let arr = JsiArray::new(len, rt);
-        Self { rt, arr, idx: 0 }
+        Self {
+            rt,
+            arr,
+            idx: 0,
+            options: Options::default(),
+        }
     }
 }
 
@@ -230,11 +315,13 @@ impl<'a, 'rt: 'a> SerializeSeq for JsiSeqSerializer<'a, 'rt> {
     where
         T: serde::Serialize,
     {
-        self.arr.set(
-            self.idx,
-            &value.serialize(JsiSerializer { rt: self.rt })?,
-            self.rt,
-        );
+        // sequences can be very long, so free the temporaries created while
+        // serializing each element as we go
+        let (arr, idx, options) = (&mut self.arr, self.idx, self.options);
+        self.rt.scope(|rt| -> Result<(), JsiSerializeError> {
+            arr.set(idx, &value.serialize(JsiSerializer { rt, options })?, rt);
+            Ok(())
+        })?;
         self.idx += 1;
 
         Ok(())
@@ -249,12 +336,18 @@ pub struct JsiTupleSerializer<'a, 'rt: 'a> {
     rt: &'a mut RuntimeHandle<'rt>,
     obj: JsiObject<'rt>,
     idx: usize,
+    options: Options,
 }
 
 impl<'a, 'rt: 'a> JsiTupleSerializer<'a, 'rt> {
     pub fn new(rt: &'a mut RuntimeHandle<'rt>) -> Self {
         let obj = JsiObject::new(rt);
-        Self { rt, obj, idx: 0 }
+        Self {
+            rt,
+            obj,
+            idx: 0,
+            options: Options::default(),
+        }
     }
 }
 
@@ -268,7 +361,10 @@ impl<'a, 'rt: 'a> SerializeTuple for JsiTupleSerializer<'a, 'rt> {
     {
         self.obj.set(
             PropName::new(self.idx.to_string().as_str(), self.rt),
-            &value.serialize(JsiSerializer { rt: self.rt })?,
+            &value.serialize(JsiSerializer {
+                rt: self.rt,
+                options: self.options,
+            })?,
             self.rt,
         );
         self.idx += 1;
@@ -342,6 +438,7 @@ pub struct JsiMapSerializer<'a, 'rt: 'a> {
     map: JsiObject<'rt>,
     setter: JsiFn<'rt>,
     current_key: Option<JsiValue<'rt>>,
+    options: Options,
 }
 
 impl<'a, 'rt: 'a> JsiMapSerializer<'a, 'rt> {
@@ -366,6 +463,7 @@ impl<'a, 'rt: 'a> JsiMapSerializer<'a, 'rt> {
             map,
             setter: map_setter,
             current_key: None,
+            options: Options::default(),
         }
     }
 }
@@ -378,7 +476,10 @@ impl<'a, 'rt: 'a> SerializeMap for JsiMapSerializer<'a, 'rt> {
     where
         T: serde::Serialize,
     {
-        let ser = JsiSerializer { rt: self.rt };
+        let ser = JsiSerializer {
+            rt: self.rt,
+            options: self.options,
+        };
         let key = key.serialize(ser)?;
         self.current_key = Some(key);
         Ok(())
@@ -388,17 +489,17 @@ impl<'a, 'rt: 'a> SerializeMap for JsiMapSerializer<'a, 'rt> {
     where
         T: serde::Serialize,
     {
-        let value = value.serialize(JsiSerializer { rt: self.rt })?;
         let key = self
             .current_key
             .take()
             .expect("tried to serialize value without serializing key first");
 
-        self.setter
-            .call_with_this(&self.map, vec![key, value], self.rt)
-            .expect("Map.set threw an exception");
-
-        Ok(())
+        let (map, setter, options) = (&self.map, &self.setter, self.options);
+        self.rt.scope(|rt| -> Result<(), JsiSerializeError> {
+            let value = value.serialize(JsiSerializer { rt, options })?;
+            setter.call_with_this(map, vec![key, value], rt)?;
+            Ok(())
+        })
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -409,12 +510,17 @@ impl<'a, 'rt: 'a> SerializeMap for JsiMapSerializer<'a, 'rt> {
 pub struct JsiStructSerializer<'a, 'rt: 'a> {
     rt: &'a mut RuntimeHandle<'rt>,
     obj: JsiObject<'rt>,
+    options: Options,
 }
 
 impl<'a, 'rt: 'a> JsiStructSerializer<'a, 'rt> {
     pub fn new(rt: &'a mut RuntimeHandle<'rt>) -> Self {
         let obj = JsiObject::new(rt);
-        Self { rt, obj }
+        Self {
+            rt,
+            obj,
+            options: Options::default(),
+        }
     }
 }
 
@@ -432,7 +538,10 @@ impl<'a, 'rt: 'a> SerializeStruct for JsiStructSerializer<'a, 'rt> {
     {
         self.obj.set(
             PropName::new(key, self.rt),
-            &value.serialize(JsiSerializer { rt: self.rt })?,
+            &value.serialize(JsiSerializer {
+                rt: self.rt,
+                options: self.options,
+            })?,
             self.rt,
         );
         Ok(())
@@ -486,6 +595,7 @@ impl<'a, 'rt: 'a> SerializeStructVariant for JsiStructVariantSerializer<'a, 'rt>
 pub enum JsiSerializeError {
     Custom(String),
     UnsizedSequence,
+    Js(DetachedJsError),
 }
 
 impl std::fmt::Display for JsiSerializeError {
@@ -493,10 +603,17 @@ impl std::fmt::Display for JsiSerializeError {
         match self {
             JsiSerializeError::Custom(s) => f.write_str(s.as_str()),
             JsiSerializeError::UnsizedSequence => f.write_str("cannot serialize unsized sequences"),
+            JsiSerializeError::Js(err) => std::fmt::Display::fmt(err, f),
         }
     }
 }
 
+impl From<JsError<'_>> for JsiSerializeError {
+    fn from(err: JsError) -> Self {
+        Self::Js(err.detach())
+    }
+}
+
 impl serde::ser::Error for JsiSerializeError {
     fn custom<T>(msg: T) -> Self
     where
@@ -506,4 +623,11 @@ impl serde::ser::Error for JsiSerializeError {
     }
 }
 
-impl std::error::Error for JsiSerializeError {}
+impl std::error::Error for JsiSerializeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsiSerializeError::Js(err) => Some(err),
+            _ => None,
+        }
+    }
+}
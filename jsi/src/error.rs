@@ -0,0 +1,680 @@
+//! # Errors
+//!
+//! Exceptions thrown by JavaScript code that was called from Rust, and errors
+//! thrown into JavaScript by Rust code that was called from JavaScript.
+
+use std::marker::PhantomData;
+
+use crate::{
+    sys, IntoValue, JsiFn, JsiObject, JsiRef, JsiString, JsiValue, PropName, RuntimeHandle,
+};
+
+/// How many levels of `cause` are read from a thrown error, in case the causes
+/// form a cycle.
+const MAX_CAUSE_DEPTH: usize = 8;
+
+/// An exception that was thrown while calling into JavaScript, or a failure in
+/// the runtime itself.
+///
+/// The `name`, `message`, `stack` and `cause` of the thrown value are read
+/// when the error is created, so they can be inspected without access to the
+/// runtime. The thrown value itself is also kept, so that it can be rethrown:
+/// if a host function returns a `JsError` (even one wrapped in
+/// [`anyhow::Context`]), the original value is thrown back to the caller (see
+/// [`IntoJsError`]).
+///
+/// A `JsError` borrows the runtime that created it, like the other handle
+/// types. To keep an error for longer (ex. in an [`anyhow::Error`], which is
+/// what happens when a `JsError` is returned with `?` from a function that
+/// returns an [`anyhow::Result`]), it is converted into a [`DetachedJsError`].
+/// For the same reason, `JsError` does not implement [`std::error::Error`]
+/// itself.
+pub struct JsError<'rt> {
+    value: cxx::UniquePtr<sys::JsiValue>,
+    /// The runtime that threw `value`, which is needed to detach the error.
+    /// Null if this is a native error.
+    rt: *mut sys::Runtime,
+    name: String,
+    message: String,
+    stack: Option<String>,
+    cause: Option<Box<JsError<'rt>>>,
+    _marker: PhantomData<&'rt ()>,
+}
+
+impl<'rt> JsError<'rt> {
+    /// The `name` of the thrown error (ex. `TypeError`), or an empty string
+    /// if the thrown value is not an object with a name or if this is a
+    /// native error.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The `message` of the thrown error, or the thrown value converted to a
+    /// string if it does not have a message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The `stack` of the thrown error, if it has one.
+    pub fn stack(&self) -> Option<&str> {
+        self.stack.as_deref()
+    }
+
+    /// The `cause` of the thrown error, if it has one.
+    pub fn cause(&self) -> Option<&JsError<'rt>> {
+        self.cause.as_deref()
+    }
+
+    /// Returns `true` if the runtime failed for a reason other than a JS
+    /// exception. Native errors do not have a thrown value.
+    pub fn is_native(&self) -> bool {
+        self.value.is_null()
+    }
+
+    /// Returns a copy of the value that was thrown.
+    pub fn value(&self, rt: &mut RuntimeHandle<'rt>) -> Option<JsiValue<'rt>> {
+        self.value
+            .as_ref()
+            .map(|value| JsiValue(sys::Value_copy(value, rt.get_inner_mut()), PhantomData))
+    }
+
+    /// Returns the value that was thrown.
+    pub fn into_value(self) -> Option<JsiValue<'rt>> {
+        if self.value.is_null() {
+            None
+        } else {
+            Some(JsiValue(self.value, PhantomData))
+        }
+    }
+
+    /// Converts this error into one that does not borrow the runtime. The
+    /// thrown value (and those of the causes) are kept in a [`JsiRef`], if
+    /// one can be created.
+    pub fn detach(self) -> DetachedJsError {
+        let value = if self.value.is_null() {
+            None
+        } else {
+            let mut rt = RuntimeHandle::new_unchecked(self.rt);
+            JsiRef::new(JsiValue(self.value, PhantomData), &mut rt).ok()
+        };
+
+        DetachedJsError {
+            value,
+            name: self.name,
+            message: self.message,
+            stack: self.stack,
+            cause: self.cause.map(|cause| Box::new(cause.detach())),
+        }
+    }
+
+    pub(crate) fn from_thrown(value: JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Self {
+        Self::from_thrown_with_depth(value, rt, 0)
+    }
+
+    fn from_thrown_with_depth(
+        value: JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+        depth: usize,
+    ) -> Self {
+        let mut name = String::new();
+        let mut message = None;
+        let mut stack = None;
+        let mut cause = None;
+
+        if let Some(obj) = value.try_into_js::<JsiObject>(rt) {
+            name = string_prop(&obj, "name", rt).unwrap_or_default();
+            message = string_prop(&obj, "message", rt);
+            stack = string_prop(&obj, "stack", rt);
+
+            if depth < MAX_CAUSE_DEPTH {
+                cause = obj
+                    .try_get(PropName::new("cause", rt), rt)
+                    .ok()
+                    .filter(|cause| !cause.is_undefined())
+                    .map(|cause| Box::new(Self::from_thrown_with_depth(cause, rt, depth + 1)));
+            }
+        }
+
+        let message = message.unwrap_or_else(|| rt.to_string(&value));
+
+        JsError {
+            value: value.0,
+            rt: rt.0,
+            name,
+            message,
+            stack,
+            cause,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn from_native(err: cxx::Exception) -> Self {
+        JsError {
+            value: cxx::UniquePtr::null(),
+            rt: std::ptr::null_mut(),
+            name: String::new(),
+            message: err.what().to_string(),
+            stack: None,
+            cause: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Converts the outcome of a call through one of the `sys` functions which
+    /// store JS exceptions in a `thrown` out-parameter.
+    pub(crate) fn check<T>(
+        res: Result<T, cxx::Exception>,
+        thrown: cxx::UniquePtr<sys::JsiValue>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<T, Self> {
+        let res = res.map_err(Self::from_native)?;
+
+        if thrown.is_null() {
+            Ok(res)
+        } else {
+            Err(Self::from_thrown(JsiValue(thrown, PhantomData), rt))
+        }
+    }
+}
+
+impl<'rt> From<JsError<'rt>> for anyhow::Error {
+    fn from(err: JsError<'rt>) -> Self {
+        anyhow::Error::new(err.detach())
+    }
+}
+
+/// A [`JsError`] that has been detached from its runtime, so that it can be
+/// kept for as long as needed and sent to other threads. It is an owned
+/// snapshot of the `name`, `message`, `stack` and `cause` of the error.
+///
+/// The thrown value is kept in a [`JsiRef`], so it can still be rethrown while
+/// its runtime is alive. Once the runtime has been destroyed, [`IntoJsError`]
+/// creates a new error object with the same name and message instead.
+pub struct DetachedJsError {
+    value: Option<JsiRef<JsiValue<'static>>>,
+    name: String,
+    message: String,
+    stack: Option<String>,
+    cause: Option<Box<DetachedJsError>>,
+}
+
+impl DetachedJsError {
+    /// See [`JsError::name`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// See [`JsError::message`].
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// See [`JsError::stack`].
+    pub fn stack(&self) -> Option<&str> {
+        self.stack.as_deref()
+    }
+
+    /// See [`JsError::cause`].
+    pub fn cause(&self) -> Option<&DetachedJsError> {
+        self.cause.as_deref()
+    }
+
+    /// See [`JsError::is_native`].
+    pub fn is_native(&self) -> bool {
+        self.value.is_none()
+    }
+
+    /// Returns the value that was thrown, or `None` if this is a native error,
+    /// if the runtime that threw it has been destroyed, or if `rt` is a
+    /// different runtime.
+    pub fn value<'rt>(&self, rt: &mut RuntimeHandle<'rt>) -> Option<JsiValue<'rt>> {
+        self.value.as_ref()?.get(rt).ok()
+    }
+
+    /// Returns the thrown value if it is still available, or else an error
+    /// object with the same name, message and cause.
+    fn to_js<'rt>(&self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        if let Some(value) = self.value(rt) {
+            return value;
+        }
+
+        let class = match self.name.as_str() {
+            "" => JsErrorClass::Error,
+            name => JsErrorClass::from_name(name),
+        };
+        let mut error = create_error(&class, &self.message, &[], rt);
+
+        if let Some(cause) = &self.cause {
+            let cause = cause.to_js(rt);
+            let _ = error.try_set(PropName::new("cause", rt), &cause, rt);
+        }
+
+        error.into_value(rt)
+    }
+}
+
+/// The class of a JS error created from a Rust error.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum JsErrorClass {
+    #[default]
+    Error,
+    TypeError,
+    RangeError,
+    SyntaxError,
+    ReferenceError,
+    /// A subclass of `Error`. If the global object has a constructor with
+    /// this name, it is used to create the error; otherwise, a plain `Error`
+    /// is created and its `name` is set to this name.
+    Custom(String),
+}
+
+impl JsErrorClass {
+    /// Gets the class with the given name, which is [`JsErrorClass::Custom`]
+    /// if it is not one of the built-in error classes.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "Error" => JsErrorClass::Error,
+            "TypeError" => JsErrorClass::TypeError,
+            "RangeError" => JsErrorClass::RangeError,
+            "SyntaxError" => JsErrorClass::SyntaxError,
+            "ReferenceError" => JsErrorClass::ReferenceError,
+            name => JsErrorClass::Custom(name.to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            JsErrorClass::Error => "Error",
+            JsErrorClass::TypeError => "TypeError",
+            JsErrorClass::RangeError => "RangeError",
+            JsErrorClass::SyntaxError => "SyntaxError",
+            JsErrorClass::ReferenceError => "ReferenceError",
+            JsErrorClass::Custom(name) => name,
+        }
+    }
+}
+
+/// The value of an extra property on a [`HostError`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorProperty {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl From<bool> for ErrorProperty {
+    fn from(b: bool) -> Self {
+        ErrorProperty::Bool(b)
+    }
+}
+
+impl From<f64> for ErrorProperty {
+    fn from(n: f64) -> Self {
+        ErrorProperty::Number(n)
+    }
+}
+
+impl From<i32> for ErrorProperty {
+    fn from(n: i32) -> Self {
+        ErrorProperty::Number(n as f64)
+    }
+}
+
+impl From<u32> for ErrorProperty {
+    fn from(n: u32) -> Self {
+        ErrorProperty::Number(n as f64)
+    }
+}
+
+impl From<String> for ErrorProperty {
+    fn from(s: String) -> Self {
+        ErrorProperty::String(s)
+    }
+}
+
+impl From<&str> for ErrorProperty {
+    fn from(s: &str) -> Self {
+        ErrorProperty::String(s.to_string())
+    }
+}
+
+impl ErrorProperty {
+    fn to_value<'rt>(&self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        match self {
+            ErrorProperty::Bool(b) => JsiValue::new_bool(*b),
+            ErrorProperty::Number(n) => JsiValue::new_number(*n),
+            ErrorProperty::String(s) => JsiValue::new_string(s, rt),
+        }
+    }
+}
+
+/// An error in Rust code called from JS (ex. a host function or host object)
+/// which is thrown as a specific class of JS error, so that JS callers can
+/// tell errors apart without parsing their messages.
+///
+/// ```no_run
+/// # use jsi::HostError;
+/// # fn f() -> anyhow::Result<()> {
+/// # let index = 7;
+/// return Err(HostError::range_error(format!("index {} is out of bounds", index))
+///     .with_code("ERR_OUT_OF_BOUNDS")
+///     .into());
+/// # }
+/// ```
+///
+/// The `source` of the error (and its own sources) become the `cause` of the
+/// JS error.
+#[derive(Debug)]
+pub struct HostError {
+    class: JsErrorClass,
+    message: String,
+    properties: Vec<(String, ErrorProperty)>,
+    source: Option<anyhow::Error>,
+}
+
+impl HostError {
+    pub fn new(class: JsErrorClass, message: impl Into<String>) -> Self {
+        HostError {
+            class,
+            message: message.into(),
+            properties: Vec::new(),
+            source: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(JsErrorClass::Error, message)
+    }
+
+    pub fn type_error(message: impl Into<String>) -> Self {
+        Self::new(JsErrorClass::TypeError, message)
+    }
+
+    pub fn range_error(message: impl Into<String>) -> Self {
+        Self::new(JsErrorClass::RangeError, message)
+    }
+
+    /// Creates an error whose class has the given name. See
+    /// [`JsErrorClass::Custom`].
+    pub fn custom(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(JsErrorClass::Custom(name.into()), message)
+    }
+
+    /// Sets the `code` property of the error.
+    pub fn with_code(self, code: impl Into<ErrorProperty>) -> Self {
+        self.with_property("code", code)
+    }
+
+    /// Sets an extra property on the error.
+    pub fn with_property(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<ErrorProperty>,
+    ) -> Self {
+        self.properties.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the error that caused this one.
+    pub fn with_source(mut self, source: impl Into<anyhow::Error>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn class(&self) -> &JsErrorClass {
+        &self.class
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn properties(&self) -> &[(String, ErrorProperty)] {
+        &self.properties
+    }
+
+    /// Creates the JS error object for this error.
+    pub fn into_object<'rt>(self, rt: &mut RuntimeHandle<'rt>) -> JsiObject<'rt> {
+        let mut error = create_error(&self.class, &self.message, &self.properties, rt);
+
+        if let Some(source) = &self.source {
+            let cause = chain_to_js(source.chain(), rt);
+            let _ = error.try_set(PropName::new("cause", rt), &cause, rt);
+        }
+
+        error
+    }
+}
+
+impl std::fmt::Display for HostError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.class.name(), self.message)
+    }
+}
+
+impl std::error::Error for HostError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Conversion of a Rust error into the value that is thrown when it is
+/// returned to JS.
+pub trait IntoJsError {
+    fn into_js_error<'rt>(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt>;
+}
+
+impl IntoJsError for HostError {
+    fn into_js_error<'rt>(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        self.into_object(rt).into_value(rt)
+    }
+}
+
+impl IntoJsError for JsError<'_> {
+    /// Returns the original thrown value, or an `Error` with the same message
+    /// if this is a native error. `rt` must be the runtime that created this
+    /// error.
+    fn into_js_error<'rt>(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        if self.value.is_null() {
+            create_error(&JsErrorClass::Error, &self.message, &[], rt).into_value(rt)
+        } else {
+            JsiValue(self.value, PhantomData)
+        }
+    }
+}
+
+impl IntoJsError for DetachedJsError {
+    /// Returns the original thrown value if its runtime is `rt`, or else a new
+    /// error object with the same name, message and cause.
+    fn into_js_error<'rt>(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        self.to_js(rt)
+    }
+}
+
+impl IntoJsError for anyhow::Error {
+    /// If the error is a [`DetachedJsError`] (even one wrapped in
+    /// [`anyhow::Context`]), it is converted as described above; in particular,
+    /// the original thrown value is returned if it belongs to `rt`.
+    ///
+    /// Otherwise, an error object is created with the outermost message of the
+    /// error. If the error is a [`HostError`] (again, even one wrapped in
+    /// context), its class and properties are used. The rest of the context
+    /// chain becomes the `cause` of the error, one link at a time.
+    fn into_js_error<'rt>(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        if let Some(js) = self.downcast_ref::<DetachedJsError>() {
+            return js.to_js(rt);
+        }
+
+        // `HostError`'s `Display` includes its class, which JS shows separately
+        let message = link_message(self.chain().next().expect("error chain is empty"));
+        let mut error = match self.downcast_ref::<HostError>() {
+            Some(host) => create_error(&host.class, &message, &host.properties, rt),
+            None => create_error(&JsErrorClass::Error, &message, &[], rt),
+        };
+
+        let mut chain = self.chain().skip(1).peekable();
+        if chain.peek().is_some() {
+            let cause = chain_to_js(chain, rt);
+            let _ = error.try_set(PropName::new("cause", rt), &cause, rt);
+        }
+
+        error.into_value(rt)
+    }
+}
+
+/// Converts an error chain into a JS error whose `cause` is the rest of the
+/// chain. The chain must not be empty.
+fn chain_to_js<'a, 'rt>(
+    mut chain: impl Iterator<Item = &'a (dyn std::error::Error + 'static)>,
+    rt: &mut RuntimeHandle<'rt>,
+) -> JsiValue<'rt> {
+    let err = chain.next().expect("error chain is empty");
+
+    if let Some(js) = err.downcast_ref::<DetachedJsError>() {
+        // the error already contains the rest of the chain as its causes
+        return js.to_js(rt);
+    }
+
+    let message = link_message(err);
+    let mut error = match err.downcast_ref::<HostError>() {
+        Some(host) => create_error(&host.class, &message, &host.properties, rt),
+        None => create_error(&JsErrorClass::Error, &message, &[], rt),
+    };
+
+    let mut chain = chain.peekable();
+    if chain.peek().is_some() {
+        let cause = chain_to_js(chain, rt);
+        let _ = error.try_set(PropName::new("cause", rt), &cause, rt);
+    }
+
+    error.into_value(rt)
+}
+
+/// The message of a single link in an error chain, without the class name that
+/// [`HostError`] adds to its `Display` output.
+fn link_message(err: &(dyn std::error::Error + 'static)) -> String {
+    match err.downcast_ref::<HostError>() {
+        Some(host) => host.message.clone(),
+        None => err.to_string(),
+    }
+}
+
+/// Creates an error object by calling the constructor for `class`. Errors are
+/// ignored (falling back to a plain `Error`, or to a plain object with `name`
+/// and `message` if scripts have removed `Error` as well) because we are
+/// already handling one.
+fn create_error<'rt>(
+    class: &JsErrorClass,
+    message: &str,
+    properties: &[(String, ErrorProperty)],
+    rt: &mut RuntimeHandle<'rt>,
+) -> JsiObject<'rt> {
+    let mut error = construct_error(class.name(), message, rt)
+        .or_else(|| {
+            let mut error = construct_error("Error", message, rt)?;
+            let name = JsiValue::new_string(class.name(), rt);
+            let _ = error.try_set(PropName::new("name", rt), &name, rt);
+            Some(error)
+        })
+        .unwrap_or_else(|| {
+            let mut error = JsiObject::new(rt);
+            let name = JsiValue::new_string(class.name(), rt);
+            let _ = error.try_set(PropName::new("name", rt), &name, rt);
+            let message = JsiValue::new_string(message, rt);
+            let _ = error.try_set(PropName::new("message", rt), &message, rt);
+            error
+        });
+
+    for (name, value) in properties {
+        let value = value.to_value(rt);
+        let _ = error.try_set(PropName::new(name, rt), &value, rt);
+    }
+
+    error
+}
+
+fn construct_error<'rt>(
+    ctor: &str,
+    message: &str,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Option<JsiObject<'rt>> {
+    let ctor = rt.global().try_get(PropName::new(ctor, rt), rt).ok()?;
+    let ctor: JsiFn = ctor.try_into_js(rt)?;
+    let message = JsiValue::new_string(message, rt);
+    let error = ctor
+        .call_as_constructor(std::iter::once(message), rt)
+        .ok()?;
+    error.try_into_js(rt)
+}
+
+/// Converts an error returned by a host function or host object into one that
+/// can be passed back to the trampoline, which throws the JS error created by
+/// [`IntoJsError`].
+pub(crate) fn into_host_error(err: anyhow::Error, rt: &mut RuntimeHandle) -> anyhow::Error {
+    sys::ThrownValue(err.into_js_error(rt).0).into()
+}
+
+/// Gets a property of a thrown object if it is a string, ignoring any errors
+/// because we are already handling one.
+fn string_prop<'rt>(
+    obj: &JsiObject<'rt>,
+    name: &str,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Option<String> {
+    let value = obj.try_get(PropName::new(name, rt), rt).ok()?;
+    let value: JsiString = value.try_into_js(rt)?;
+    Some(rt.to_string(&value))
+}
+
+impl std::fmt::Debug for JsError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsError")
+            .field("name", &self.name)
+            .field("message", &self.message)
+            .field("stack", &self.stack)
+            .field("cause", &self.cause)
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Display for JsError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_error(&self.name, &self.message, f)
+    }
+}
+
+impl std::fmt::Debug for DetachedJsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DetachedJsError")
+            .field("name", &self.name)
+            .field("message", &self.message)
+            .field("stack", &self.stack)
+            .field("cause", &self.cause)
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Display for DetachedJsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_error(&self.name, &self.message, f)
+    }
+}
+
+impl std::error::Error for DetachedJsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause
+            .as_deref()
+            .map(|cause| cause as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Same format as `Error.prototype.toString()`.
+fn fmt_error(name: &str, message: &str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match (name.is_empty(), message.is_empty()) {
+        (true, _) => f.write_str(message),
+        (false, true) => f.write_str(name),
+        (false, false) => write!(f, "{}: {}", name, message),
+    }
+}
@@ -1,5 +1,6 @@
+use crate::error::into_host_error;
 use crate::host_function::UserHostFunction;
-use crate::{sys, IntoValue, JsiObject, JsiValue, PropName, RuntimeHandle};
+use crate::{sys, IntoValue, JsError, JsiObject, JsiValue, PropName, RuntimeHandle};
 use anyhow::{bail, Context};
 use std::marker::PhantomData;
 use std::pin::Pin;
@@ -15,40 +16,44 @@ impl<'rt> JsiFn<'rt> {
         &self,
         args: T,
         rt: &mut RuntimeHandle<'rt>,
-    ) -> Result<JsiValue<'rt>, cxx::Exception> {
+    ) -> Result<JsiValue<'rt>, JsError<'rt>> {
         let mut args_cxx = sys::create_value_vector();
         for arg in args {
             sys::push_value_vector(args_cxx.pin_mut(), arg.0);
         }
 
-        Ok(JsiValue(
-            sys::Function_call(
-                self.0.as_ref().unwrap(),
-                rt.get_inner_mut(),
-                args_cxx.as_ref().unwrap(),
-            )?,
-            PhantomData,
-        ))
+        let mut thrown = cxx::UniquePtr::null();
+
+        let value = sys::Function_call(
+            self.0.as_ref().unwrap(),
+            rt.get_inner_mut(),
+            args_cxx.as_ref().unwrap(),
+            &mut thrown,
+        );
+
+        JsError::check(value, thrown, rt).map(|value| JsiValue(value, PhantomData))
     }
 
     pub fn call_as_constructor<T: IntoIterator<Item = JsiValue<'rt>>>(
         &self,
         args: T,
         rt: &mut RuntimeHandle<'rt>,
-    ) -> Result<JsiValue<'rt>, cxx::Exception> {
+    ) -> Result<JsiValue<'rt>, JsError<'rt>> {
         let mut args_cxx = sys::create_value_vector();
         for arg in args {
             sys::push_value_vector(args_cxx.pin_mut(), arg.0);
         }
 
-        Ok(JsiValue(
-            sys::Function_callAsConstructor(
-                self.0.as_ref().unwrap(),
-                rt.get_inner_mut(),
-                args_cxx.as_ref().unwrap(),
-            )?,
-            PhantomData,
-        ))
+        let mut thrown = cxx::UniquePtr::null();
+
+        let value = sys::Function_callAsConstructor(
+            self.0.as_ref().unwrap(),
+            rt.get_inner_mut(),
+            args_cxx.as_ref().unwrap(),
+            &mut thrown,
+        );
+
+        JsError::check(value, thrown, rt).map(|value| JsiValue(value, PhantomData))
     }
 
     pub fn call_with_this<'a, 'ret, T: IntoIterator<Item = JsiValue<'a>>>(
@@ -56,7 +61,7 @@ impl<'rt> JsiFn<'rt> {
         this: &JsiObject,
         args: T,
         rt: &mut RuntimeHandle<'rt>,
-    ) -> Result<JsiValue<'ret>, cxx::Exception>
+    ) -> Result<JsiValue<'ret>, JsError<'rt>>
     where
         'rt: 'a,
         'rt: 'ret,
@@ -66,15 +71,17 @@ impl<'rt> JsiFn<'rt> {
             sys::push_value_vector(args_cxx.pin_mut(), arg.0);
         }
 
-        Ok(JsiValue(
-            sys::Function_callWithThis(
-                self.0.as_ref().unwrap(),
-                rt.get_inner_mut(),
-                this.0.as_ref().unwrap(),
-                args_cxx.as_ref().unwrap(),
-            )?,
-            PhantomData,
-        ))
+        let mut thrown = cxx::UniquePtr::null();
+
+        let value = sys::Function_callWithThis(
+            self.0.as_ref().unwrap(),
+            rt.get_inner_mut(),
+            this.0.as_ref().unwrap(),
+            args_cxx.as_ref().unwrap(),
+            &mut thrown,
+        );
+
+        JsError::check(value, thrown, rt).map(|value| JsiValue(value, PhantomData))
     }
 
     pub fn from_host_fn(
@@ -110,8 +117,10 @@ impl<'rt> JsiFn<'rt> {
                 //     args
                 // );
 
-                let val = body(this, args, &mut rt)?;
-                Ok(val.0)
+                match body(this, args, &mut rt) {
+                    Ok(val) => Ok(val.0),
+                    Err(err) => Err(into_host_error(err, &mut rt)),
+                }
             });
 
         let cb = Box::into_raw(Box::new(cb)) as *mut _;
@@ -6,6 +6,7 @@
 use anyhow::bail;
 use std::{marker::PhantomData, pin::Pin};
 
+use crate::error::into_host_error;
 use crate::{sys, IntoValue, JsTaskCallback, JsiValue, PropName, RuntimeHandle};
 use sys::CallInvokerCallback;
 
@@ -55,7 +56,8 @@ pub struct SharedJsiHostObject<'rt>(
     pub(crate) PhantomData<&'rt mut ()>,
 );
 
-/// Helper trait for implementing a host object in Rust
+/// Helper trait for implementing a host object in Rust. Errors returned from
+/// `get` and `set` are thrown into JS using [`IntoJsError`](crate::IntoJsError).
 pub trait UserHostObject<'rt> {
     fn get(
         &mut self,
@@ -93,8 +95,10 @@ impl<'rt, T: UserHostObject<'rt>> sys::HostObjectImpl for UserHostObjectWrapper<
     ) -> anyhow::Result<cxx::UniquePtr<sys::JsiValue>> {
         let mut rt = RuntimeHandle::new_unchecked(unsafe { rt.get_unchecked_mut() as *mut _ });
         let name = PropName(sys::PropNameID_copy(name, rt.get_inner_mut()), PhantomData);
-        let value = UserHostObject::get(&mut self.0, name, &mut rt)?;
-        Ok(value.0)
+        match UserHostObject::get(&mut self.0, name, &mut rt) {
+            Ok(value) => Ok(value.0),
+            Err(err) => Err(into_host_error(err, &mut rt)),
+        }
     }
 
     fn set(
@@ -107,6 +111,7 @@ impl<'rt, T: UserHostObject<'rt>> sys::HostObjectImpl for UserHostObjectWrapper<
         let name = PropName(sys::PropNameID_copy(name, rt.get_inner_mut()), PhantomData);
         let value = JsiValue(sys::Value_copy(value, rt.get_inner_mut()), PhantomData);
         UserHostObject::set(&mut self.0, name, value, &mut rt)
+            .map_err(|err| into_host_error(err, &mut rt))
     }
 
     fn properties(&mut self, rt: Pin<&mut sys::Runtime>) -> Vec<cxx::UniquePtr<sys::PropNameID>> {
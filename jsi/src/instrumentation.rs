@@ -0,0 +1,52 @@
+//! # Instrumentation
+//!
+//! Types returned by the runtime's debugging and profiling APIs.
+
+use std::collections::HashMap;
+
+/// Statistics about the runtime's garbage-collected heap. Each field is `None`
+/// if the runtime does not report that statistic; Hermes reports all of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeapInfo {
+    /// Bytes currently allocated for JS values.
+    pub allocated_bytes: Option<u64>,
+    /// Size of the heap, including space which is not in use.
+    pub heap_size: Option<u64>,
+    /// Bytes allocated outside of the heap which are owned by JS values (ex.
+    /// the contents of an `ArrayBuffer`).
+    pub external_bytes: Option<u64>,
+    /// Bytes allocated since the runtime was created.
+    pub total_allocated_bytes: Option<u64>,
+    /// The largest value that `allocated_bytes` has reached.
+    pub peak_allocated_bytes: Option<u64>,
+    /// Number of garbage collections since the runtime was created.
+    pub num_collections: Option<u64>,
+    /// Every statistic reported by the runtime, under its original name (ex.
+    /// `hermes_allocatedBytes`).
+    pub raw: HashMap<String, i64>,
+}
+
+impl HeapInfo {
+    pub(crate) fn from_raw(raw: HashMap<String, i64>) -> Self {
+        let mut info = HeapInfo::default();
+
+        for (key, value) in &raw {
+            // Hermes prefixes its statistics with the name of the engine
+            let name = key.strip_prefix("hermes_").unwrap_or(key);
+            let value = u64::try_from(*value).ok();
+
+            match name {
+                "allocatedBytes" => info.allocated_bytes = value,
+                "heapSize" => info.heap_size = value,
+                "externalBytes" => info.external_bytes = value,
+                "totalAllocatedBytes" => info.total_allocated_bytes = value,
+                "peakAllocatedBytes" => info.peak_allocated_bytes = value,
+                "numCollections" => info.num_collections = value,
+                _ => {}
+            }
+        }
+
+        info.raw = raw;
+        info
+    }
+}
@@ -0,0 +1,130 @@
+use thiserror::Error;
+
+use crate::{ConversionError, FromValue, JsiBigInt, JsiValue, RuntimeHandle, TryFromValue};
+
+/// A JS value could not be converted to a Rust integer type without losing
+/// information.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum IntegerConversionError {
+    #[error("expected an integer, got NaN")]
+    NaN,
+
+    #[error("expected an integer, got {0}")]
+    NotIntegral(f64),
+
+    #[error("{value} is out of range for {ty}")]
+    OutOfRange { value: String, ty: &'static str },
+
+    #[error("expected a number or BigInt, got {0}")]
+    NotANumber(&'static str),
+
+    #[error("{0} is outside the safe integer range")]
+    Unsafe(String),
+}
+
+/// `Number.MAX_SAFE_INTEGER`: integers up to this size (in either direction)
+/// can be represented exactly by a JS number.
+pub(crate) const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+/// Converts an integer to a number, or returns `None` if it is outside of the
+/// range where numbers are exact.
+pub(crate) fn safe_number(n: impl TryInto<i128>) -> Option<f64> {
+    let n = n.try_into().ok()?;
+    (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER)
+        .contains(&n)
+        .then_some(n as f64)
+}
+
+/// Converts a number or a `BigInt` to an integer, failing instead of
+/// truncating or saturating.
+pub(crate) fn checked_integer<'rt, T>(
+    value: &JsiValue<'rt>,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<T, IntegerConversionError>
+where
+    T: TryFrom<i128> + TryFrom<u128> + Bounds,
+{
+    let out_of_range = |value: String| IntegerConversionError::OutOfRange {
+        value,
+        ty: std::any::type_name::<T>(),
+    };
+
+    if value.is_bigint() {
+        let big: JsiBigInt = FromValue::from_value(value, rt).unwrap();
+
+        if let Some(n) = big.to_i128(rt) {
+            return <T as TryFrom<i128>>::try_from(n).map_err(|_| out_of_range(n.to_string()));
+        }
+
+        return match big.to_u128(rt) {
+            Some(n) => <T as TryFrom<u128>>::try_from(n).map_err(|_| out_of_range(n.to_string())),
+            None => Err(out_of_range(
+                big.to_string_radix(10, rt)
+                    .unwrap_or_else(|| "BigInt".to_string()),
+            )),
+        };
+    }
+
+    let n = match value.0.get_number() {
+        Ok(n) => n,
+        Err(_) => return Err(IntegerConversionError::NotANumber(value.type_name())),
+    };
+
+    if n.is_nan() {
+        return Err(IntegerConversionError::NaN);
+    }
+
+    if n.fract() != 0. || n.is_infinite() {
+        return Err(IntegerConversionError::NotIntegral(n));
+    }
+
+    // adding 1 to the maximum is exact for small types, and is absorbed by
+    // rounding for large ones (ex. `i64::MAX as f64` is already 2^63)
+    if n < T::MIN || n >= T::MAX + 1. {
+        return Err(out_of_range(n.to_string()));
+    }
+
+    let converted = if n < 0. {
+        <T as TryFrom<i128>>::try_from(n as i128).ok()
+    } else {
+        <T as TryFrom<u128>>::try_from(n as u128).ok()
+    };
+
+    converted.ok_or_else(|| out_of_range(n.to_string()))
+}
+
+/// The range of an integer type as `f64`s.
+pub(crate) trait Bounds {
+    const MIN: f64;
+    const MAX: f64;
+}
+
+macro_rules! impl_integer {
+    ($($ty: ty),*) => {
+        $(
+            impl Bounds for $ty {
+                const MIN: f64 = <$ty>::MIN as f64;
+                const MAX: f64 = <$ty>::MAX as f64;
+            }
+
+            /// Accepts a number which is an integer in range, or a `BigInt`
+            /// in range.
+            impl<'rt> TryFromValue<'rt> for $ty {
+                fn try_from_value(
+                    value: &JsiValue<'rt>,
+                    rt: &mut RuntimeHandle<'rt>,
+                ) -> Result<Self, ConversionError> {
+                    checked_integer(value, rt).map_err(|err| match err {
+                        IntegerConversionError::NotANumber(_) => {
+                            ConversionError::new(stringify!($ty), value, rt)
+                        }
+                        err => ConversionError::new(stringify!($ty), value, rt)
+                            .with_reason(err.to_string()),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
@@ -1,59 +1,197 @@
 use std::{future::Future, pin::Pin};
 
 #[cfg(feature = "macros")]
-pub use jsi_macros::host_object;
+pub use jsi_macros::{host_object, FromValue, IntoValue};
 pub use jsi_sys as sys;
+pub use sys::{set_panic_hook, take_panic_hook, CaughtPanic, PanicHook};
 
 // allows us to use the proc macros inside this crate
 extern crate self as jsi;
 
 mod array;
 mod array_buffer;
+mod bigint;
+mod buffer;
 mod call_invoker;
+mod classify;
 #[cfg(feature = "serde")]
 mod convert;
+mod conversion;
+mod error;
 mod function;
 mod host_function;
 mod host_object;
+mod instrumentation;
+mod integer;
 mod object;
+mod operations;
 mod prop_name;
+mod property;
+mod reference;
 mod runtime;
+mod script;
 mod string;
 mod symbol;
 mod value;
+mod weak_object;
 
 pub use array::*;
 pub use array_buffer::*;
+pub use bigint::*;
+pub use buffer::*;
 pub use call_invoker::*;
+pub use classify::*;
 #[cfg(feature = "serde")]
 pub use convert::*;
+pub use conversion::*;
+pub use error::*;
 pub use function::*;
 pub use host_function::*;
 pub use host_object::*;
+pub use instrumentation::*;
+pub use integer::*;
 pub use object::*;
+pub use operations::*;
 pub use prop_name::*;
+pub use property::*;
+pub use reference::*;
 pub use runtime::*;
+pub use script::*;
 pub use string::*;
 pub use symbol::*;
 pub use value::*;
+pub use weak_object::*;
 
-/// Creates a JavaScript `Error` object with the given string as the message.
+/// Creates a JavaScript error object, formatting the message like
+/// [`format!`]. An error class can be given before the message; classes which
+/// are not built in are handled like [`JsErrorClass::Custom`].
+///
+/// ```no_run
+/// # use jsi::{js_error, RuntimeHandle};
+/// # fn f(rt: &mut RuntimeHandle) {
+/// # let index = 7;
+/// let error = js_error!(rt, "something went wrong");
+/// let error = js_error!(rt, RangeError, "index {} is out of bounds", index);
+/// let error = js_error!(rt, NotFoundError, "no such file");
+/// # }
+/// ```
 #[macro_export]
 macro_rules! js_error {
-    ($rt: expr, $err: tt) => {{
-        let mut rt = $rt;
-        let error_ctor = rt.global().get(PropName::new("Error", rt), rt);
-        let error_ctor: ::jsi::JsiFn = error_ctor.try_into_js(rt).unwrap();
-        let error_str = format!($err);
-        let error_str = ::jsi::JsiValue::new_string(error_str.as_str(), rt);
-        let error = error_ctor
-            .call_as_constructor(::std::iter::once(error_str), rt)
-            .expect("Error constructor threw an exception");
-        let error: ::jsi::JsiObject = error
-            .try_into_js(rt)
-            .expect("Error constructor returned a non-object");
-        error
+    ($rt: expr, $class: ident, $($fmt: tt)+) => {{
+        let class = ::jsi::JsErrorClass::from_name(::std::stringify!($class));
+        ::jsi::HostError::new(class, ::std::format!($($fmt)+)).into_object($rt)
     }};
+    ($rt: expr, $($fmt: tt)+) => {{
+        ::jsi::HostError::error(::std::format!($($fmt)+)).into_object($rt)
+    }};
+}
+
+/// Creates a JavaScript object from a literal. Values can be any expression
+/// that implements [`IntoValue`], or nested object (`{ .. }`) and array
+/// (`[ .. ]`) literals. Keys are identifiers or literals.
+///
+/// ```no_run
+/// # use jsi::{js_object, JsiFn, RuntimeHandle};
+/// # fn f<'rt>(rt: &mut RuntimeHandle<'rt>, callback: JsiFn<'rt>) {
+/// let obj = js_object!(rt, {
+///     name: "x",
+///     count: 3,
+///     nested: { ok: true, list: [1, 2, 3] },
+///     "content-type": "text/plain",
+///     cb: callback,
+/// });
+/// # }
+/// ```
+///
+/// `rt` is used once for each property, so it should be a variable and not
+/// an expression with side effects.
+#[macro_export]
+macro_rules! js_object {
+    ($rt: expr, { $($props: tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut obj = ::jsi::JsiObject::new($rt);
+        ::jsi::__js_object_props!(obj, $rt, $($props)*);
+        obj
+    }};
+}
+
+/// Creates a JavaScript array from a literal. Items can be any expression
+/// that implements [`IntoValue`], or nested object (`{ .. }`) and array
+/// (`[ .. ]`) literals, like in [`js_object!`].
+///
+/// ```no_run
+/// # use jsi::{js_array, RuntimeHandle};
+/// # fn f(rt: &mut RuntimeHandle) {
+/// let arr = js_array!(rt, ["a", { b: 2 }, [3, 4]]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! js_array {
+    ($rt: expr, [ $($items: tt)* ]) => {{
+        #[allow(unused_mut)]
+        let mut items: ::std::vec::Vec<::jsi::JsiValue> = ::std::vec::Vec::new();
+        ::jsi::__js_array_items!(items, $rt, $($items)*);
+
+        let mut arr = ::jsi::JsiArray::new(items.len(), $rt);
+        for (idx, item) in items.iter().enumerate() {
+            arr.set(idx, item, $rt);
+        }
+        arr
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __js_object_props {
+    (@set $obj: ident, $rt: expr, $key: tt, $value: expr) => {
+        let value = ::jsi::IntoValue::into_value($value, $rt);
+        $obj.set(::jsi::PropName::new(::jsi::__js_key!($key), $rt), &value, $rt);
+    };
+    ($obj: ident, $rt: expr, ) => {};
+    ($obj: ident, $rt: expr, $key: tt : { $($value: tt)* } $(, $($rest: tt)*)?) => {
+        ::jsi::__js_object_props!(@set $obj, $rt, $key, ::jsi::js_object!($rt, { $($value)* }));
+        ::jsi::__js_object_props!($obj, $rt, $($($rest)*)?);
+    };
+    ($obj: ident, $rt: expr, $key: tt : [ $($value: tt)* ] $(, $($rest: tt)*)?) => {
+        ::jsi::__js_object_props!(@set $obj, $rt, $key, ::jsi::js_array!($rt, [ $($value)* ]));
+        ::jsi::__js_object_props!($obj, $rt, $($($rest)*)?);
+    };
+    ($obj: ident, $rt: expr, $key: tt : $value: expr $(, $($rest: tt)*)?) => {
+        ::jsi::__js_object_props!(@set $obj, $rt, $key, $value);
+        ::jsi::__js_object_props!($obj, $rt, $($($rest)*)?);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __js_array_items {
+    ($items: ident, $rt: expr, ) => {};
+    ($items: ident, $rt: expr, { $($item: tt)* } $(, $($rest: tt)*)?) => {
+        let item = ::jsi::js_object!($rt, { $($item)* });
+        $items.push(::jsi::IntoValue::into_value(item, $rt));
+        ::jsi::__js_array_items!($items, $rt, $($($rest)*)?);
+    };
+    ($items: ident, $rt: expr, [ $($item: tt)* ] $(, $($rest: tt)*)?) => {
+        let item = ::jsi::js_array!($rt, [ $($item)* ]);
+        $items.push(::jsi::IntoValue::into_value(item, $rt));
+        ::jsi::__js_array_items!($items, $rt, $($($rest)*)?);
+    };
+    ($items: ident, $rt: expr, $item: expr $(, $($rest: tt)*)?) => {
+        let item = ::jsi::IntoValue::into_value($item, $rt);
+        $items.push(item);
+        ::jsi::__js_array_items!($items, $rt, $($($rest)*)?);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __js_key {
+    ($key: ident) => {
+        ::std::stringify!($key)
+    };
+    ($key: literal) => {
+        ::std::concat!($key)
+    };
 }
 
 pub type JsTaskCallback = Box<
@@ -5,8 +5,8 @@ use crate::array_buffer::JsiArrayBuffer;
 use crate::function::JsiFn;
 use crate::host_object::{OwnedJsiHostObject, SharedJsiHostObject};
 use crate::{
-    sys, FromValue, JsiValue, OwnedJsiUserHostObject, PropName, RuntimeHandle,
-    SharedJsiUserHostObject,
+    sys, ConversionError, JsError, JsiValue, OwnedJsiUserHostObject, PropName, RuntimeHandle,
+    SharedJsiUserHostObject, TryFromValue,
 };
 
 unsafe impl<'rt> Send for JsiObject<'rt> {}
@@ -22,15 +22,28 @@ impl<'rt> JsiObject<'rt> {
         JsiObject(sys::Object_create(rt.get_inner_mut()), PhantomData)
     }
 
+    /// Gets a property of this object. Panics if a getter throws an
+    /// exception; use [`JsiObject::try_get`] to handle it instead.
     pub fn get(&self, prop: PropName, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
-        JsiValue(
-            sys::Object_getProperty(
-                self.0.as_ref().unwrap(),
-                rt.get_inner_mut(),
-                prop.0.as_ref().unwrap(),
-            ),
-            PhantomData,
-        )
+        self.try_get(prop, rt)
+            .expect("exception thrown while getting property")
+    }
+
+    pub fn try_get(
+        &self,
+        prop: PropName,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<JsiValue<'rt>, JsError<'rt>> {
+        let mut thrown = cxx::UniquePtr::null();
+
+        let value = sys::Object_getProperty(
+            self.0.as_ref().unwrap(),
+            rt.get_inner_mut(),
+            prop.0.as_ref().unwrap(),
+            &mut thrown,
+        );
+
+        JsError::check(value, thrown, rt).map(|value| JsiValue(value, PhantomData))
     }
 
     pub fn has(&self, prop: PropName, rt: &mut RuntimeHandle<'rt>) -> bool {
@@ -38,18 +51,38 @@ impl<'rt> JsiObject<'rt> {
             .has_property(rt.get_inner_mut(), prop.0.as_ref().unwrap())
     }
 
+    /// Sets a property of this object. Panics if a setter throws an
+    /// exception; use [`JsiObject::try_set`] to handle it instead.
     pub fn set(&mut self, prop: PropName, value: &JsiValue, rt: &mut RuntimeHandle<'rt>) {
-        sys::Object_setProperty(
+        self.try_set(prop, value, rt)
+            .expect("exception thrown while setting property")
+    }
+
+    pub fn try_set(
+        &mut self,
+        prop: PropName,
+        value: &JsiValue,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<(), JsError<'rt>> {
+        let mut thrown = cxx::UniquePtr::null();
+
+        let res = sys::Object_setProperty(
             self.0.pin_mut(),
             rt.get_inner_mut(),
             prop.0.as_ref().unwrap(),
             value.0.as_ref().unwrap(),
-        )
+            &mut thrown,
+        );
+
+        JsError::check(res, thrown, rt)
     }
 
-    pub fn properties(&mut self, rt: &mut RuntimeHandle<'rt>) -> JsiArray<'rt> {
+    /// The names of the enumerable properties of this object, including
+    /// inherited ones, like a `for..in` loop. See [`JsiObject::keys`] for
+    /// other kinds of properties.
+    pub fn properties(&self, rt: &mut RuntimeHandle<'rt>) -> JsiArray<'rt> {
         JsiArray(
-            sys::Object_getPropertyNames(self.0.pin_mut(), rt.get_inner_mut()),
+            sys::Object_getPropertyNames(&*self.0, rt.get_inner_mut()),
             PhantomData,
         )
     }
@@ -66,6 +99,12 @@ impl<'rt> JsiObject<'rt> {
         self.0.is_function(rt.get_inner_mut())
     }
 
+    /// Returns `true` for host objects, whether they are implemented in Rust
+    /// or not.
+    pub fn is_host_object(&self, rt: &mut RuntimeHandle<'rt>) -> bool {
+        sys::Object_isHostObject(&*self.0, rt.get_inner_mut())
+    }
+
     pub fn is_instance(&mut self, ctor: JsiFn, rt: &mut RuntimeHandle<'rt>) -> bool {
         self.0
             .pin_mut()
@@ -74,6 +113,9 @@ impl<'rt> JsiObject<'rt> {
 }
 
 pub trait FromObject<'rt>: Sized {
+    /// Describes this type in [`ConversionError`]s.
+    const TYPE_NAME: &'static str = "object";
+
     fn from_object(obj: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self>;
 }
 
@@ -82,6 +124,8 @@ pub trait IntoObject<'rt> {
 }
 
 impl<'rt> FromObject<'rt> for JsiArray<'rt> {
+    const TYPE_NAME: &'static str = "array";
+
     fn from_object(obj: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
         sys::Object_asArray(&*obj.0, rt.get_inner_mut())
             .ok()
@@ -90,6 +134,8 @@ impl<'rt> FromObject<'rt> for JsiArray<'rt> {
 }
 
 impl<'rt> FromObject<'rt> for JsiArrayBuffer<'rt> {
+    const TYPE_NAME: &'static str = "ArrayBuffer";
+
     fn from_object(ojb: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
         sys::Object_asArrayBuffer(&*ojb.0, rt.get_inner_mut())
             .ok()
@@ -98,6 +144,8 @@ impl<'rt> FromObject<'rt> for JsiArrayBuffer<'rt> {
 }
 
 impl<'rt> FromObject<'rt> for JsiFn<'rt> {
+    const TYPE_NAME: &'static str = "function";
+
     fn from_object(obj: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
         sys::Object_asFunction(&*obj.0, rt.get_inner_mut())
             .ok()
@@ -105,16 +153,18 @@ impl<'rt> FromObject<'rt> for JsiFn<'rt> {
     }
 }
 
-impl<'rt, T: FromValue<'rt>> FromObject<'rt> for Vec<T> {
-    fn from_object(obj: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
-        let arr: Option<JsiArray> = FromObject::from_object(obj, rt);
-        arr.and_then(|arr| {
-            let arr: Vec<_> = arr.iter(rt).collect();
+impl<'rt, T: TryFromValue<'rt>> TryFromValue<'rt> for Vec<T> {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        let arr: JsiArray = value.convert(rt)?;
+        let arr: Vec<_> = arr.iter(rt).collect();
 
-            arr.into_iter()
-                .map(|it| FromValue::from_value(&it, rt))
-                .collect()
-        })
+        arr.into_iter()
+            .enumerate()
+            .map(|(i, it)| T::try_from_value(&it, rt).map_err(|err| err.at_index(i)))
+            .collect()
     }
 }
 
@@ -187,6 +237,8 @@ impl<'rt> IntoObject<'rt> for SharedJsiUserHostObject<'rt> {
 }
 
 impl<'rt> FromObject<'rt> for SharedJsiHostObject<'rt> {
+    const TYPE_NAME: &'static str = "host object";
+
     fn from_object(obj: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
         sys::Object_asHostObject(&*obj.0, rt.get_inner_mut())
             .ok()
@@ -195,6 +247,8 @@ impl<'rt> FromObject<'rt> for SharedJsiHostObject<'rt> {
 }
 
 impl<'rt> FromObject<'rt> for SharedJsiUserHostObject<'rt> {
+    const TYPE_NAME: &'static str = "host object";
+
     fn from_object(obj: &JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
         let obj: Option<SharedJsiHostObject> = FromObject::from_object(obj, rt);
         obj.and_then(|obj| obj.try_into().ok())
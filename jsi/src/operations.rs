@@ -0,0 +1,292 @@
+//! # Abstract operations
+//!
+//! Implementations of the abstract operations from the ECMAScript
+//! specification (ex. `ToBoolean`), so that Rust code can coerce and compare
+//! values exactly like the JS that it replaces. Where the specification calls
+//! for parsing (ex. `StringToNumber`), the runtime's own `Number` and `BigInt`
+//! functions are used.
+
+use std::marker::PhantomData;
+
+use crate::{
+    sys, HostError, IntoValue, JsError, JsiBigInt, JsiFn, JsiObject, JsiSymbol, JsiValue, PropName,
+    RuntimeHandle,
+};
+
+/// The hint passed to [`JsiValue::to_primitive`], which decides whether
+/// `valueOf` or `toString` is tried first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreferredType {
+    #[default]
+    Default,
+    Number,
+    String,
+}
+
+impl PreferredType {
+    fn name(self) -> &'static str {
+        match self {
+            PreferredType::Default => "default",
+            PreferredType::Number => "number",
+            PreferredType::String => "string",
+        }
+    }
+}
+
+/// The language types of the specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Type {
+    Undefined,
+    Null,
+    Boolean,
+    Number,
+    String,
+    Symbol,
+    BigInt,
+    Object,
+}
+
+impl<'rt> JsiValue<'rt> {
+    fn spec_type(&self) -> Type {
+        if self.is_undefined() {
+            Type::Undefined
+        } else if self.is_null() {
+            Type::Null
+        } else if self.is_bool() {
+            Type::Boolean
+        } else if self.is_number() {
+            Type::Number
+        } else if self.is_string() {
+            Type::String
+        } else if self.is_symbol() {
+            Type::Symbol
+        } else if self.is_bigint() {
+            Type::BigInt
+        } else {
+            Type::Object
+        }
+    }
+
+    /// The result of the `typeof` operator.
+    pub fn type_of(&self, rt: &mut RuntimeHandle<'rt>) -> &'static str {
+        match self.spec_type() {
+            Type::Undefined => "undefined",
+            Type::Null => "object",
+            Type::Boolean => "boolean",
+            Type::Number => "number",
+            Type::String => "string",
+            Type::Symbol => "symbol",
+            Type::BigInt => "bigint",
+            Type::Object => {
+                let obj = sys::Value_asObject(&*self.0, rt.get_inner_mut()).unwrap();
+                if obj.is_function(rt.get_inner_mut()) {
+                    "function"
+                } else {
+                    "object"
+                }
+            }
+        }
+    }
+
+    /// `ToBoolean`, the conversion used by `if` and `!`.
+    pub fn to_boolean(&self, rt: &mut RuntimeHandle<'rt>) -> bool {
+        match self.spec_type() {
+            Type::Undefined | Type::Null => false,
+            Type::Boolean => self.0.get_bool().unwrap(),
+            Type::Number => {
+                let n = self.0.get_number().unwrap();
+                !(n == 0. || n.is_nan())
+            }
+            Type::String => {
+                let empty = JsiValue::new_string("", rt);
+                !self.strict_equals(&empty, rt)
+            }
+            Type::BigInt => {
+                let zero = JsiBigInt::from_i64(0, rt).into_value(rt);
+                !self.strict_equals(&zero, rt)
+            }
+            Type::Symbol | Type::Object => true,
+        }
+    }
+
+    /// `ToNumber`, the conversion used by unary `+`. Fails for symbols and
+    /// BigInts, and if converting an object to a primitive throws.
+    pub fn to_number(&self, rt: &mut RuntimeHandle<'rt>) -> Result<f64, JsError<'rt>> {
+        match self.spec_type() {
+            Type::Undefined => Ok(f64::NAN),
+            Type::Null => Ok(0.),
+            Type::Boolean => Ok(if self.0.get_bool().unwrap() { 1. } else { 0. }),
+            Type::Number => Ok(self.0.get_number().unwrap()),
+            Type::String => {
+                let number = global_fn("Number", rt)?;
+                let n = number.call(std::iter::once(rt.clone(self)), rt)?;
+                match n.0.get_number() {
+                    Ok(n) => Ok(n),
+                    Err(_) => Err(type_error("Number did not return a number", rt)),
+                }
+            }
+            Type::Symbol => Err(type_error("Cannot convert a Symbol value to a number", rt)),
+            Type::BigInt => Err(type_error("Cannot convert a BigInt value to a number", rt)),
+            Type::Object => self.to_primitive(PreferredType::Number, rt)?.to_number(rt),
+        }
+    }
+
+    /// `ToPrimitive`. Objects are converted with their `Symbol.toPrimitive`
+    /// method if they have one, and otherwise with `valueOf` and `toString`.
+    /// Other values are returned as they are.
+    pub fn to_primitive(
+        &self,
+        hint: PreferredType,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<JsiValue<'rt>, JsError<'rt>> {
+        let obj = match sys::Value_asObject(&*self.0, rt.get_inner_mut()) {
+            Ok(raw) => JsiObject(raw, PhantomData),
+            Err(_) => return Ok(rt.clone(self)),
+        };
+
+        let exotic = get_to_primitive(&obj, rt)?;
+
+        if !(exotic.is_undefined() || exotic.is_null()) {
+            let exotic: JsiFn = exotic
+                .try_into_js(rt)
+                .ok_or_else(|| type_error("Symbol.toPrimitive is not a function", rt))?;
+
+            let hint = JsiValue::new_string(hint.name(), rt);
+            let result = exotic.call_with_this(&obj, std::iter::once(hint), rt)?;
+
+            return if result.is_object() {
+                Err(type_error("Cannot convert object to primitive value", rt))
+            } else {
+                Ok(result)
+            };
+        }
+
+        let methods = match hint {
+            PreferredType::String => ["toString", "valueOf"],
+            PreferredType::Default | PreferredType::Number => ["valueOf", "toString"],
+        };
+
+        for name in methods {
+            let method = obj.try_get(PropName::new(name, rt), rt)?;
+
+            if let Some(method) = method.try_into_js::<JsiFn>(rt) {
+                let result = method.call_with_this(&obj, std::iter::empty(), rt)?;
+
+                if !result.is_object() {
+                    return Ok(result);
+                }
+            }
+        }
+
+        Err(type_error("Cannot convert object to primitive value", rt))
+    }
+
+    /// `IsStrictlyEqual`, the `===` operator.
+    pub fn strict_equals(&self, other: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> bool {
+        sys::Value_compare(rt.get_inner_mut(), &*self.0, &*other.0)
+    }
+
+    /// `IsLooselyEqual`, the `==` operator. Fails if converting an object to
+    /// a primitive throws.
+    pub fn loose_equals(
+        &self,
+        other: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<bool, JsError<'rt>> {
+        use Type::*;
+
+        match (self.spec_type(), other.spec_type()) {
+            (x, y) if x == y => Ok(self.strict_equals(other, rt)),
+            (Undefined, Null) | (Null, Undefined) => Ok(true),
+            (Number, String) => {
+                let y = other.to_number(rt)?;
+                Ok(self.0.get_number().unwrap() == y)
+            }
+            (String, Number) | (String, BigInt) => other.loose_equals(self, rt),
+            (BigInt, String) => match string_to_bigint(other, rt)? {
+                Some(n) => Ok(self.strict_equals(&n, rt)),
+                None => Ok(false),
+            },
+            (Boolean, _) => {
+                let x = JsiValue::new_number(self.to_number(rt)?);
+                x.loose_equals(other, rt)
+            }
+            (_, Boolean) => other.loose_equals(self, rt),
+            (String | Number | BigInt | Symbol, Object) => {
+                let y = other.to_primitive(PreferredType::Default, rt)?;
+                self.loose_equals(&y, rt)
+            }
+            (Object, String | Number | BigInt | Symbol) => other.loose_equals(self, rt),
+            (BigInt, Number) => bigint_equals_number(self, other.0.get_number().unwrap(), rt),
+            (Number, BigInt) => bigint_equals_number(other, self.0.get_number().unwrap(), rt),
+            _ => Ok(false),
+        }
+    }
+
+    /// `SameValueZero`, the comparison used by `Array.prototype.includes` and
+    /// by `Map` and `Set` keys. Unlike `===`, `NaN` is equal to itself.
+    pub fn same_value_zero(&self, other: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> bool {
+        match (self.0.get_number(), other.0.get_number()) {
+            (Ok(x), Ok(y)) => (x.is_nan() && y.is_nan()) || x == y,
+            _ => self.strict_equals(other, rt),
+        }
+    }
+}
+
+/// Gets a function like `Number` from the global object. Scripts can replace
+/// or delete these, which is reported as a `TypeError`.
+fn global_fn<'rt>(name: &str, rt: &mut RuntimeHandle<'rt>) -> Result<JsiFn<'rt>, JsError<'rt>> {
+    rt.global()
+        .try_get(PropName::new(name, rt), rt)?
+        .try_into_js(rt)
+        .ok_or_else(|| type_error(&format!("{} is not a function", name), rt))
+}
+
+pub(crate) fn type_error<'rt>(message: &str, rt: &mut RuntimeHandle<'rt>) -> JsError<'rt> {
+    let error = HostError::type_error(message)
+        .into_object(rt)
+        .into_value(rt);
+    JsError::from_thrown(error, rt)
+}
+
+/// `GetMethod(obj, @@toPrimitive)`. If `Symbol.toPrimitive` cannot be found
+/// because `globalThis.Symbol` has been replaced, objects are treated as if
+/// they had no `Symbol.toPrimitive` method.
+fn get_to_primitive<'rt>(
+    obj: &JsiObject<'rt>,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<JsiValue<'rt>, JsError<'rt>> {
+    let to_primitive = match JsiSymbol::to_primitive(rt) {
+        Some(to_primitive) => to_primitive,
+        None => return Ok(JsiValue::new_undefined()),
+    };
+    obj.try_get(PropName::from_symbol(to_primitive, rt), rt)
+}
+
+/// `StringToBigInt`, or `None` if the string is not an integer.
+fn string_to_bigint<'rt>(
+    value: &JsiValue<'rt>,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<Option<JsiValue<'rt>>, JsError<'rt>> {
+    let bigint = global_fn("BigInt", rt)?;
+    // the only exception BigInt throws for strings is a SyntaxError
+    Ok(bigint.call(std::iter::once(rt.clone(value)), rt).ok())
+}
+
+fn bigint_equals_number<'rt>(
+    bigint: &JsiValue<'rt>,
+    n: f64,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<bool, JsError<'rt>> {
+    if !n.is_finite() || n.fract() != 0. {
+        return Ok(false);
+    }
+
+    // integral numbers are converted to BigInts exactly
+    let n = match global_fn("BigInt", rt)?.call(std::iter::once(JsiValue::new_number(n)), rt) {
+        Ok(n) => n,
+        Err(_) => return Ok(false),
+    };
+
+    Ok(bigint.strict_equals(&n, rt))
+}
@@ -1,6 +1,7 @@
 use std::marker::PhantomData;
 
 use crate::string::JsiString;
+use crate::symbol::JsiSymbol;
 use crate::{sys, RuntimeClone, RuntimeDisplay, RuntimeEq, RuntimeHandle};
 
 /// A `PropName`, which is used to retrieve properties from `Object`s.
@@ -23,6 +24,16 @@ impl<'rt> PropName<'rt> {
             PhantomData,
         )
     }
+
+    /// Creates a property name from a symbol, so that symbol-keyed properties
+    /// can be used with [`JsiObject::get`](crate::JsiObject::get) and
+    /// [`JsiObject::set`](crate::JsiObject::set).
+    pub fn from_symbol(symbol: JsiSymbol<'rt>, rt: &mut RuntimeHandle<'rt>) -> Self {
+        PropName(
+            sys::PropNameID_forSymbol(rt.get_inner_mut(), &*symbol.0),
+            PhantomData,
+        )
+    }
 }
 
 impl RuntimeClone<'_> for PropName<'_> {
@@ -0,0 +1,599 @@
+//! # Property reflection
+//!
+//! Deleting, defining and listing properties, property descriptors and
+//! prototypes. JSI does not expose most of these directly, so they are
+//! implemented with the runtime's own `Object` and `Reflect` functions.
+
+use std::collections::HashSet;
+
+use crate::operations::type_error;
+
+use crate::{
+    AsValue, IntoValue, JsError, JsiFn, JsiObject, JsiString, JsiSymbol, JsiValue, PropName,
+    RuntimeHandle,
+};
+
+/// Which properties are listed by [`JsiObject::keys_with`],
+/// [`JsiObject::values_with`] and [`JsiObject::entries_with`]. The default
+/// lists the same properties as `Object.keys`: own, enumerable properties
+/// with string names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyFilter {
+    /// Also list properties inherited from prototypes, like `for..in`.
+    /// Properties that are shadowed by a property of the object itself (or
+    /// of a closer prototype) are only listed once.
+    pub inherited: bool,
+    /// Also list non-enumerable properties.
+    pub non_enumerable: bool,
+    /// Also list properties whose names are symbols.
+    pub symbols: bool,
+}
+
+/// A property descriptor, as used by `Object.defineProperty`. The
+/// constructors create a property that is not writable, enumerable or
+/// configurable, like `Object.defineProperty` does for missing fields.
+///
+/// ```no_run
+/// # use jsi::{JsiObject, JsiValue, PropName, PropertyDescriptor, RuntimeHandle};
+/// # fn f<'rt>(obj: &mut JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> anyhow::Result<()> {
+/// let version = PropertyDescriptor::data(JsiValue::new_number(2.));
+/// obj.define_property(PropName::new("version", rt), version, rt)?;
+///
+/// let name = PropertyDescriptor::getter(
+///     |_this, rt| Ok(JsiValue::new_string("jsi-rs", rt)),
+///     rt,
+/// )
+/// .enumerable(true);
+/// obj.define_property(PropName::new("name", rt), name, rt)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PropertyDescriptor<'rt> {
+    pub value: PropertyValue<'rt>,
+    pub enumerable: bool,
+    pub configurable: bool,
+}
+
+/// The value of a [`PropertyDescriptor`].
+pub enum PropertyValue<'rt> {
+    Data {
+        value: JsiValue<'rt>,
+        writable: bool,
+    },
+    Accessor {
+        get: Option<JsiFn<'rt>>,
+        set: Option<JsiFn<'rt>>,
+    },
+}
+
+impl<'rt> PropertyDescriptor<'rt> {
+    pub fn data(value: JsiValue<'rt>) -> Self {
+        Self::new(PropertyValue::Data {
+            value,
+            writable: false,
+        })
+    }
+
+    pub fn accessor(get: Option<JsiFn<'rt>>, set: Option<JsiFn<'rt>>) -> Self {
+        Self::new(PropertyValue::Accessor { get, set })
+    }
+
+    /// An accessor property with a getter implemented in Rust, which is
+    /// called with the value of `this`.
+    pub fn getter(
+        get: impl FnMut(JsiValue<'rt>, &mut RuntimeHandle<'rt>) -> anyhow::Result<JsiValue<'rt>> + 'rt,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Self {
+        Self::accessor(Some(getter_fn(get, rt)), None)
+    }
+
+    /// An accessor property with a setter implemented in Rust, which is
+    /// called with the value of `this` and the new value.
+    pub fn setter(
+        set: impl FnMut(JsiValue<'rt>, JsiValue<'rt>, &mut RuntimeHandle<'rt>) -> anyhow::Result<()>
+            + 'rt,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Self {
+        Self::accessor(None, Some(setter_fn(set, rt)))
+    }
+
+    /// Adds a setter implemented in Rust to this property, which becomes an
+    /// accessor property if it was not one already.
+    pub fn with_setter(
+        mut self,
+        set: impl FnMut(JsiValue<'rt>, JsiValue<'rt>, &mut RuntimeHandle<'rt>) -> anyhow::Result<()>
+            + 'rt,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Self {
+        let set = Some(setter_fn(set, rt));
+        self.value = match self.value {
+            PropertyValue::Accessor { get, .. } => PropertyValue::Accessor { get, set },
+            PropertyValue::Data { .. } => PropertyValue::Accessor { get: None, set },
+        };
+        self
+    }
+
+    /// Sets whether the value can be changed by assignment. Has no effect on
+    /// accessor properties.
+    pub fn writable(mut self, writable: bool) -> Self {
+        if let PropertyValue::Data { writable: w, .. } = &mut self.value {
+            *w = writable;
+        }
+        self
+    }
+
+    /// Sets whether the property shows up in `Object.keys` and `for..in`.
+    pub fn enumerable(mut self, enumerable: bool) -> Self {
+        self.enumerable = enumerable;
+        self
+    }
+
+    /// Sets whether the property can be deleted or redefined.
+    pub fn configurable(mut self, configurable: bool) -> Self {
+        self.configurable = configurable;
+        self
+    }
+
+    fn new(value: PropertyValue<'rt>) -> Self {
+        PropertyDescriptor {
+            value,
+            enumerable: false,
+            configurable: false,
+        }
+    }
+
+    /// The descriptor object has no prototype, so that properties like
+    /// `get` which scripts add to `Object.prototype` are not inherited.
+    fn into_object(self, rt: &mut RuntimeHandle<'rt>) -> Result<JsiObject<'rt>, JsError<'rt>> {
+        let mut obj = JsiObject::with_prototype(None, rt)?;
+
+        match self.value {
+            PropertyValue::Data { value, writable } => {
+                obj.try_set(PropName::new("value", rt), &value, rt)?;
+                obj.try_set(
+                    PropName::new("writable", rt),
+                    &JsiValue::new_bool(writable),
+                    rt,
+                )?;
+            }
+            PropertyValue::Accessor { get, set } => {
+                if let Some(get) = get {
+                    let get = get.into_value(rt);
+                    obj.try_set(PropName::new("get", rt), &get, rt)?;
+                }
+                if let Some(set) = set {
+                    let set = set.into_value(rt);
+                    obj.try_set(PropName::new("set", rt), &set, rt)?;
+                }
+            }
+        }
+
+        let enumerable = JsiValue::new_bool(self.enumerable);
+        obj.try_set(PropName::new("enumerable", rt), &enumerable, rt)?;
+        let configurable = JsiValue::new_bool(self.configurable);
+        obj.try_set(PropName::new("configurable", rt), &configurable, rt)?;
+
+        Ok(obj)
+    }
+
+    fn from_object(
+        obj: &JsiObject<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, JsError<'rt>> {
+        let flag = |name: &str, rt: &mut RuntimeHandle<'rt>| -> Result<bool, JsError<'rt>> {
+            Ok(obj.try_get(PropName::new(name, rt), rt)?.to_boolean(rt))
+        };
+
+        let value = if obj.has(PropName::new("value", rt), rt) {
+            PropertyValue::Data {
+                value: obj.try_get(PropName::new("value", rt), rt)?,
+                writable: flag("writable", rt)?,
+            }
+        } else {
+            PropertyValue::Accessor {
+                get: obj.try_get(PropName::new("get", rt), rt)?.try_into_js(rt),
+                set: obj.try_get(PropName::new("set", rt), rt)?.try_into_js(rt),
+            }
+        };
+
+        Ok(PropertyDescriptor {
+            value,
+            enumerable: flag("enumerable", rt)?,
+            configurable: flag("configurable", rt)?,
+        })
+    }
+}
+
+impl<'rt> JsiObject<'rt> {
+    /// Creates an object with the given prototype, like `Object.create`.
+    pub fn with_prototype(
+        proto: Option<&JsiObject<'rt>>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, JsError<'rt>> {
+        let proto = proto_value(proto, rt);
+        builtin("Object", "create", rt)?
+            .call(std::iter::once(proto), rt)?
+            .try_into_js(rt)
+            .ok_or_else(|| type_error("Object.create did not return an object", rt))
+    }
+
+    /// Deletes a property of this object. Returns `false` if the property
+    /// could not be deleted because it is not configurable.
+    pub fn delete(
+        &mut self,
+        prop: PropName,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<bool, JsError<'rt>> {
+        let this = self.as_value(rt);
+        let key = prop_key(&prop, rt)?;
+        let res = builtin("Reflect", "deleteProperty", rt)?.call([this, key], rt)?;
+        Ok(res.to_boolean(rt))
+    }
+
+    /// Defines or redefines a property of this object, like
+    /// `Object.defineProperty`. Fails if the property exists and is not
+    /// configurable, or if this object is not extensible.
+    pub fn define_property(
+        &mut self,
+        prop: PropName,
+        descriptor: PropertyDescriptor<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<(), JsError<'rt>> {
+        let this = self.as_value(rt);
+        let key = prop_key(&prop, rt)?;
+        let descriptor = descriptor.into_object(rt)?.into_value(rt);
+        builtin("Object", "defineProperty", rt)?.call([this, key, descriptor], rt)?;
+        Ok(())
+    }
+
+    /// Returns the descriptor of an own property of this object, or `None`
+    /// if the property does not exist or is inherited.
+    pub fn get_own_property_descriptor(
+        &self,
+        prop: PropName,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Option<PropertyDescriptor<'rt>>, JsError<'rt>> {
+        let this = self.as_value(rt);
+        let key = prop_key(&prop, rt)?;
+        let descriptor =
+            builtin("Object", "getOwnPropertyDescriptor", rt)?.call([this, key], rt)?;
+
+        match descriptor.try_into_js::<JsiObject>(rt) {
+            Some(descriptor) => PropertyDescriptor::from_object(&descriptor, rt).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the prototype of this object, or `None` if it is `null`.
+    pub fn get_prototype(
+        &self,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Option<JsiObject<'rt>>, JsError<'rt>> {
+        let this = self.as_value(rt);
+        let proto = builtin("Object", "getPrototypeOf", rt)?.call(std::iter::once(this), rt)?;
+        Ok(proto.try_into_js(rt))
+    }
+
+    /// Sets the prototype of this object. Fails if this object is not
+    /// extensible, or if this would create a cycle of prototypes.
+    pub fn set_prototype(
+        &mut self,
+        proto: Option<&JsiObject<'rt>>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<(), JsError<'rt>> {
+        let this = self.as_value(rt);
+        let proto = proto_value(proto, rt);
+        builtin("Object", "setPrototypeOf", rt)?.call([this, proto], rt)?;
+        Ok(())
+    }
+
+    /// Prevents properties from being added, removed or changed, like
+    /// `Object.freeze`.
+    pub fn freeze(&mut self, rt: &mut RuntimeHandle<'rt>) -> Result<(), JsError<'rt>> {
+        let this = self.as_value(rt);
+        builtin("Object", "freeze", rt)?.call(std::iter::once(this), rt)?;
+        Ok(())
+    }
+
+    /// Prevents properties from being added or removed, like `Object.seal`.
+    pub fn seal(&mut self, rt: &mut RuntimeHandle<'rt>) -> Result<(), JsError<'rt>> {
+        let this = self.as_value(rt);
+        builtin("Object", "seal", rt)?.call(std::iter::once(this), rt)?;
+        Ok(())
+    }
+
+    pub fn is_frozen(&self, rt: &mut RuntimeHandle<'rt>) -> Result<bool, JsError<'rt>> {
+        let this = self.as_value(rt);
+        let res = builtin("Object", "isFrozen", rt)?.call(std::iter::once(this), rt)?;
+        Ok(res.to_boolean(rt))
+    }
+
+    pub fn is_sealed(&self, rt: &mut RuntimeHandle<'rt>) -> Result<bool, JsError<'rt>> {
+        let this = self.as_value(rt);
+        let res = builtin("Object", "isSealed", rt)?.call(std::iter::once(this), rt)?;
+        Ok(res.to_boolean(rt))
+    }
+}
+
+impl<'rt> JsiObject<'rt> {
+    /// The names of the own, enumerable properties of this object whose
+    /// names are strings, like `Object.keys`.
+    pub fn keys(
+        &self,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<impl Iterator<Item = PropName<'rt>>, JsError<'rt>> {
+        self.keys_with(KeyFilter::default(), rt)
+    }
+
+    /// The names of the properties of this object that are selected by
+    /// `filter`, in the same order as in JS. Own properties are listed
+    /// before inherited ones.
+    pub fn keys_with(
+        &self,
+        filter: KeyFilter,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<impl Iterator<Item = PropName<'rt>>, JsError<'rt>> {
+        let keys = collect_keys(self, filter, rt)?;
+        Ok(keys
+            .into_iter()
+            .map(|key| key_to_prop(&key, rt))
+            .collect::<Vec<_>>()
+            .into_iter())
+    }
+
+    /// The values of the own, enumerable properties of this object whose
+    /// names are strings, like `Object.values`.
+    pub fn values(
+        &self,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<impl Iterator<Item = JsiValue<'rt>>, JsError<'rt>> {
+        self.values_with(KeyFilter::default(), rt)
+    }
+
+    /// The values of the properties of this object that are selected by
+    /// `filter`. Values are read when this is called, so getters that throw
+    /// cause an error here.
+    pub fn values_with(
+        &self,
+        filter: KeyFilter,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<impl Iterator<Item = JsiValue<'rt>>, JsError<'rt>> {
+        Ok(self.entries_with(filter, rt)?.map(|(_, value)| value))
+    }
+
+    /// The names and values of the own, enumerable properties of this object
+    /// whose names are strings, like `Object.entries`.
+    ///
+    /// ```no_run
+    /// # use jsi::{JsiObject, RuntimeHandle};
+    /// # fn f<'rt>(obj: JsiObject<'rt>, rt: &mut RuntimeHandle<'rt>) -> anyhow::Result<()> {
+    /// for (name, value) in obj.entries(rt)? {
+    ///     println!("{} = {}", rt.to_string(&name), rt.to_string(&value));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entries(
+        &self,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<impl Iterator<Item = (PropName<'rt>, JsiValue<'rt>)>, JsError<'rt>> {
+        self.entries_with(KeyFilter::default(), rt)
+    }
+
+    /// The names and values of the properties of this object that are
+    /// selected by `filter`. Values are read when this is called, so getters
+    /// that throw cause an error here.
+    pub fn entries_with(
+        &self,
+        filter: KeyFilter,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<impl Iterator<Item = (PropName<'rt>, JsiValue<'rt>)>, JsError<'rt>> {
+        let keys = collect_keys(self, filter, rt)?;
+        let mut entries = Vec::with_capacity(keys.len());
+
+        for key in keys {
+            let prop = key_to_prop(&key, rt);
+            let value = self.try_get(rt.clone(&prop), rt)?;
+            entries.push((prop, value));
+        }
+
+        Ok(entries.into_iter())
+    }
+}
+
+/// Lists property names as strings and symbols, walking up the prototype
+/// chain if `filter.inherited` is set.
+fn collect_keys<'rt>(
+    obj: &JsiObject<'rt>,
+    filter: KeyFilter,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<Vec<JsiValue<'rt>>, JsError<'rt>> {
+    let own_keys = builtin("Reflect", "ownKeys", rt)?;
+    let prototype: JsiObject = builtin_object("Object", rt)?
+        .try_get(PropName::new("prototype", rt), rt)?
+        .try_into_js(rt)
+        .ok_or_else(|| type_error("Object.prototype is not an object", rt))?;
+    let is_enumerable: JsiFn = prototype
+        .try_get(PropName::new("propertyIsEnumerable", rt), rt)?
+        .try_into_js(rt)
+        .ok_or_else(|| {
+            type_error(
+                "Object.prototype.propertyIsEnumerable is not a function",
+                rt,
+            )
+        })?;
+
+    // keys from one call to `Reflect.ownKeys` are unique, so only keys of
+    // different levels of the prototype chain have to be compared. Strings
+    // are compared by their UTF-16 code units, which unlike UTF-8 keeps
+    // names with lone surrogates apart.
+    let mut seen_strings: HashSet<Vec<u16>> = HashSet::new();
+    let mut seen_symbols: Vec<JsiValue> = Vec::new();
+    let mut keys = Vec::new();
+    let mut proto: Option<JsiObject> = None;
+
+    loop {
+        let level = proto.as_ref().unwrap_or(obj);
+        let level_keys = own_keys_of(&own_keys, level, rt)?;
+
+        for key in level_keys {
+            if filter.inherited {
+                let shadowed = if key.is_symbol() {
+                    let shadowed = seen_symbols
+                        .iter()
+                        .any(|other| other.strict_equals(&key, rt));
+                    if !shadowed {
+                        seen_symbols.push(rt.clone(&key));
+                    }
+                    shadowed
+                } else {
+                    let string: JsiString = key.try_into_js(rt).unwrap();
+                    !seen_strings.insert(string.to_utf16(rt))
+                };
+
+                if shadowed {
+                    continue;
+                }
+            }
+
+            if key.is_symbol() && !filter.symbols {
+                continue;
+            }
+
+            if !filter.non_enumerable {
+                let enumerable =
+                    is_enumerable.call_with_this(level, std::iter::once(rt.clone(&key)), rt)?;
+                if !enumerable.to_boolean(rt) {
+                    continue;
+                }
+            }
+
+            keys.push(key);
+        }
+
+        if !filter.inherited {
+            break;
+        }
+
+        match level.get_prototype(rt)? {
+            Some(next) => proto = Some(next),
+            None => break,
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Converts a string or symbol from `Reflect.ownKeys` into a property name.
+fn key_to_prop<'rt>(key: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> PropName<'rt> {
+    if key.is_symbol() {
+        let symbol: JsiSymbol = key.try_into_js(rt).unwrap();
+        PropName::from_symbol(symbol, rt)
+    } else {
+        let string: JsiString = key.try_into_js(rt).unwrap();
+        PropName::from_string(string, rt)
+    }
+}
+
+/// Gets an object like `Reflect` from the global object.
+fn builtin_object<'rt>(
+    object: &str,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<JsiObject<'rt>, JsError<'rt>> {
+    rt.global()
+        .try_get(PropName::new(object, rt), rt)?
+        .try_into_js(rt)
+        .ok_or_else(|| type_error(&format!("{} is not an object", object), rt))
+}
+
+/// Gets a function like `Object.defineProperty` from the global object.
+/// Scripts can replace or delete these, which is reported as a `TypeError`.
+fn builtin<'rt>(
+    object: &str,
+    method: &str,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<JsiFn<'rt>, JsError<'rt>> {
+    builtin_object(object, rt)?
+        .try_get(PropName::new(method, rt), rt)?
+        .try_into_js(rt)
+        .ok_or_else(|| type_error(&format!("{}.{} is not a function", object, method), rt))
+}
+
+/// Calls `Reflect.ownKeys`, checking that it returned strings and symbols
+/// in case it has been replaced.
+fn own_keys_of<'rt>(
+    own_keys: &JsiFn<'rt>,
+    obj: &JsiObject<'rt>,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<Vec<JsiValue<'rt>>, JsError<'rt>> {
+    let keys: Option<Vec<JsiValue>> = own_keys
+        .call(std::iter::once(obj.as_value(rt)), rt)?
+        .try_into_js(rt);
+
+    match keys {
+        Some(keys) if keys.iter().all(|key| key.is_string() || key.is_symbol()) => Ok(keys),
+        _ => Err(type_error(
+            "Reflect.ownKeys did not return an array of property keys",
+            rt,
+        )),
+    }
+}
+
+/// Converts a property name into the string or symbol that it stands for,
+/// by defining it on an empty object and reading it back with
+/// `Reflect.ownKeys`. JSI cannot convert property names into values, and
+/// going through UTF-8 would lose symbols. The object has no prototype, so
+/// that `__proto__` and setters defined on `Object.prototype` are not
+/// called instead of creating an own property.
+fn prop_key<'rt>(
+    prop: &PropName,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<JsiValue<'rt>, JsError<'rt>> {
+    let mut obj = JsiObject::with_prototype(None, rt)?;
+    obj.try_set(rt.clone(prop), &JsiValue::new_undefined(), rt)?;
+
+    let own_keys = builtin("Reflect", "ownKeys", rt)?;
+    own_keys_of(&own_keys, &obj, rt)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| type_error("Reflect.ownKeys did not return the property name", rt))
+}
+
+fn proto_value<'rt>(proto: Option<&JsiObject<'rt>>, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+    match proto {
+        Some(proto) => proto.as_value(rt),
+        None => JsiValue::new_null(),
+    }
+}
+
+fn getter_fn<'rt>(
+    mut get: impl FnMut(JsiValue<'rt>, &mut RuntimeHandle<'rt>) -> anyhow::Result<JsiValue<'rt>> + 'rt,
+    rt: &mut RuntimeHandle<'rt>,
+) -> JsiFn<'rt> {
+    JsiFn::from_host_fn(
+        &PropName::new("get", rt),
+        0,
+        Box::new(move |this, _args, rt| get(this, rt)),
+        rt,
+    )
+}
+
+fn setter_fn<'rt>(
+    mut set: impl FnMut(JsiValue<'rt>, JsiValue<'rt>, &mut RuntimeHandle<'rt>) -> anyhow::Result<()>
+        + 'rt,
+    rt: &mut RuntimeHandle<'rt>,
+) -> JsiFn<'rt> {
+    JsiFn::from_host_fn(
+        &PropName::new("set", rt),
+        1,
+        Box::new(move |this, args, rt| {
+            let value = args
+                .into_iter()
+                .next()
+                .unwrap_or_else(JsiValue::new_undefined);
+            set(this, value, rt)?;
+            Ok(JsiValue::new_undefined())
+        }),
+        rt,
+    )
+}
@@ -0,0 +1,321 @@
+//! # Persistent references
+//!
+//! Handles like [`JsiObject`] borrow the runtime for `'rt`, so they cannot be
+//! kept in long-lived Rust structs. A [`JsiRef`] has no lifetime: the value is
+//! kept alive by a registry object that belongs to the runtime, and the
+//! reference is resolved back to a handle whenever a [`RuntimeHandle`] is
+//! available again, for example inside a host function.
+//!
+//! The registry owns a host object which is finalized when the runtime is
+//! destroyed, which is how a `JsiRef` finds out that its runtime is gone.
+
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, Weak};
+
+use thiserror::Error;
+
+use crate::{
+    IntoValue, JsiArray, JsiArrayBuffer, JsiBigInt, JsiFn, JsiObject, JsiString, JsiSymbol,
+    JsiValue, PropName, PropertyDescriptor, RuntimeHandle, SharedJsiUserHostObject, TryFromValue,
+    UserHostObject,
+};
+
+/// The key of the registry in the global object, passed to `Symbol.for`. The
+/// registry is defined as a non-enumerable, non-writable and non-configurable
+/// property.
+const REGISTRY_KEY: &str = "jsi-rs.references";
+
+/// Handle types that can be stored in a [`JsiRef`]. `Handle<'rt>` is the
+/// same type, with the lifetime of the runtime that it is resolved in.
+pub trait Persistent {
+    type Handle<'rt>: IntoValue<'rt> + TryFromValue<'rt>;
+}
+
+macro_rules! impl_persistent {
+    ($($ty:ident),*) => {
+        $(
+            impl Persistent for $ty<'_> {
+                type Handle<'rt> = $ty<'rt>;
+            }
+        )*
+    };
+}
+
+impl_persistent!(
+    JsiValue,
+    JsiObject,
+    JsiFn,
+    JsiArray,
+    JsiArrayBuffer,
+    JsiString,
+    JsiSymbol,
+    JsiBigInt
+);
+
+/// An error from resolving a [`JsiRef`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsiRefError {
+    /// The runtime that created the reference has been destroyed, for
+    /// example because the JS bundle was reloaded.
+    #[error("the runtime that created this reference has been destroyed")]
+    RuntimeDestroyed,
+    /// The reference was resolved in a different runtime than the one that
+    /// created it.
+    #[error("this reference belongs to a different runtime")]
+    WrongRuntime,
+    /// The registry could not be found or created, because
+    /// `globalThis.Symbol.for` is missing or has been replaced.
+    #[error("the reference registry is keyed by `Symbol.for`, which is not available")]
+    SymbolUnavailable,
+    /// The runtime has no reference registry, so the reference cannot belong
+    /// to it.
+    #[error("the reference registry is missing from this runtime")]
+    RegistryMissing,
+    /// The registry could not be created or updated, because a script has
+    /// taken its key, frozen it, or replaced the built-ins that are used to
+    /// define it.
+    #[error("the reference registry cannot be used, it was modified by a script")]
+    RegistryUnavailable,
+    /// A script replaced the value in the registry with a value of another
+    /// type.
+    #[error("the referenced value does not have the expected type")]
+    WrongType,
+}
+
+/// A strong reference to a JS value that is not tied to a lifetime, like
+/// `napi_ref` in Node-API. `T` is the type of handle that is stored, with any
+/// lifetime (ex. `JsiRef<JsiFn<'static>>`).
+///
+/// ```no_run
+/// # use jsi::{JsiFn, JsiRef, JsiValue, RuntimeHandle};
+/// struct Listener {
+///     callback: JsiRef<JsiFn<'static>>,
+/// }
+///
+/// impl Listener {
+///     fn notify(&self, rt: &mut RuntimeHandle) -> anyhow::Result<()> {
+///         let callback = self.callback.get(rt)?;
+///         callback.call(std::iter::empty(), rt)?;
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// The value is kept alive until the `JsiRef` is dropped. Dropping does not
+/// need the runtime, so it is safe to drop a `JsiRef` from any thread or
+/// after the runtime has been destroyed; the value is released the next time
+/// a reference is created or resolved in the same runtime.
+pub struct JsiRef<T: Persistent> {
+    id: usize,
+    registry: Weak<Registry>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Persistent> JsiRef<T> {
+    /// Creates a reference to `value`. Fails only if the registry cannot be
+    /// created or updated, see [`JsiRefError::SymbolUnavailable`] and
+    /// [`JsiRefError::RegistryUnavailable`].
+    pub fn new<'rt>(
+        value: T::Handle<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, JsiRefError> {
+        let (mut store, registry) = Registry::get_or_create(rt)?;
+        registry.sweep(&mut store, rt)?;
+
+        let id = registry.slots.lock().unwrap().allocate();
+        let value = value.into_value(rt);
+        if store
+            .try_set(PropName::new(&id.to_string(), rt), &value, rt)
+            .is_err()
+        {
+            registry.slots.lock().unwrap().free.push(id);
+            return Err(JsiRefError::RegistryUnavailable);
+        }
+
+        Ok(JsiRef {
+            id,
+            registry: Arc::downgrade(&registry),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns a handle to the value. Fails if the runtime that created this
+    /// reference has been destroyed, or if `rt` is not that runtime.
+    pub fn get<'rt>(&self, rt: &mut RuntimeHandle<'rt>) -> Result<T::Handle<'rt>, JsiRefError> {
+        let own = self
+            .registry
+            .upgrade()
+            .ok_or(JsiRefError::RuntimeDestroyed)?;
+
+        let (mut store, registry) = Registry::get(rt)?.ok_or(JsiRefError::RegistryMissing)?;
+        if !Arc::ptr_eq(&own, &registry) {
+            return Err(JsiRefError::WrongRuntime);
+        }
+
+        registry.sweep(&mut store, rt)?;
+
+        let value = store
+            .try_get(PropName::new(&self.id.to_string(), rt), rt)
+            .map_err(|_| JsiRefError::WrongType)?;
+        <T::Handle<'rt>>::try_from_value(&value, rt).map_err(|_| JsiRefError::WrongType)
+    }
+}
+
+impl<T: Persistent> Drop for JsiRef<T> {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            registry.slots.lock().unwrap().released.push(self.id);
+        }
+    }
+}
+
+impl<T: Persistent> std::fmt::Debug for JsiRef<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsiRef")
+            .field("id", &self.id)
+            .field("alive", &(self.registry.strong_count() > 0))
+            .finish()
+    }
+}
+
+/// The state of a runtime's registry that lives on the Rust side. Values are
+/// kept in the registry object in JS, with their slot numbers as keys.
+#[derive(Default)]
+struct Registry {
+    slots: Mutex<Slots>,
+}
+
+#[derive(Default)]
+struct Slots {
+    next: usize,
+    /// Slots that can be reused.
+    free: Vec<usize>,
+    /// Slots whose `JsiRef` has been dropped, but whose values are still
+    /// stored in the registry object.
+    released: Vec<usize>,
+}
+
+impl Slots {
+    fn allocate(&mut self) -> usize {
+        self.free.pop().unwrap_or_else(|| {
+            self.next += 1;
+            self.next - 1
+        })
+    }
+}
+
+impl Registry {
+    fn get<'rt>(
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Option<(JsiObject<'rt>, Arc<Registry>)>, JsiRefError> {
+        let key = registry_key(rt)?;
+        Ok(Self::get_with_key(key, rt))
+    }
+
+    fn get_with_key<'rt>(
+        key: PropName<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Option<(JsiObject<'rt>, Arc<Registry>)> {
+        let store: JsiObject = rt.global().try_get(key, rt).ok()?.try_into_js(rt)?;
+        let token: SharedJsiUserHostObject = store
+            .try_get(PropName::new("token", rt), rt)
+            .ok()?
+            .try_into_js(rt)?;
+        let registry = token.get_inner::<RegistryToken>()?.0.clone();
+        Some((store, registry))
+    }
+
+    fn get_or_create<'rt>(
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<(JsiObject<'rt>, Arc<Registry>), JsiRefError> {
+        if let Some(existing) = Self::get(rt)? {
+            return Ok(existing);
+        }
+
+        let registry = Arc::new(Registry::default());
+        let token = RegistryToken(registry.clone()).into_value(rt);
+
+        // scripts can still reach the registry with `Symbol.for`, but they
+        // cannot replace or delete it or its token. It has no prototype, so
+        // that setters on `Object.prototype` do not see the stored values.
+        let mut store =
+            JsiObject::with_prototype(None, rt).map_err(|_| JsiRefError::RegistryUnavailable)?;
+        store
+            .define_property(
+                PropName::new("token", rt),
+                PropertyDescriptor::data(token),
+                rt,
+            )
+            .map_err(|_| JsiRefError::RegistryUnavailable)?;
+
+        let key = registry_key(rt)?;
+        let value = store.into_value(rt);
+        let descriptor = PropertyDescriptor::data(rt.clone(&value));
+        rt.global()
+            .define_property(key, descriptor, rt)
+            .map_err(|_| JsiRefError::RegistryUnavailable)?;
+
+        let store = value.try_into_js(rt).unwrap();
+        Ok((store, registry))
+    }
+
+    /// Removes the values of dropped references from the registry object.
+    /// Slots that could not be cleared are kept for the next sweep.
+    fn sweep<'rt>(
+        &self,
+        store: &mut JsiObject<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<(), JsiRefError> {
+        let mut released = std::mem::take(&mut self.slots.lock().unwrap().released);
+
+        while let Some(&id) = released.last() {
+            let cleared = store.try_set(
+                PropName::new(&id.to_string(), rt),
+                &JsiValue::new_undefined(),
+                rt,
+            );
+
+            if cleared.is_err() {
+                self.slots.lock().unwrap().released.extend(released);
+                return Err(JsiRefError::RegistryUnavailable);
+            }
+
+            released.pop();
+            self.slots.lock().unwrap().free.push(id);
+        }
+
+        Ok(())
+    }
+}
+
+fn registry_key<'rt>(rt: &mut RuntimeHandle<'rt>) -> Result<PropName<'rt>, JsiRefError> {
+    let symbol = JsiSymbol::for_key(REGISTRY_KEY, rt).ok_or(JsiRefError::SymbolUnavailable)?;
+    Ok(PropName::from_symbol(symbol, rt))
+}
+
+/// A host object that owns the Rust side of the registry, and drops it when
+/// the runtime is destroyed.
+struct RegistryToken(Arc<Registry>);
+
+impl<'rt> UserHostObject<'rt> for RegistryToken {
+    fn get(
+        &mut self,
+        _name: PropName<'rt>,
+        _rt: &mut RuntimeHandle<'rt>,
+    ) -> anyhow::Result<JsiValue<'rt>> {
+        Ok(JsiValue::new_undefined())
+    }
+
+    fn set(
+        &mut self,
+        _name: PropName<'rt>,
+        _value: JsiValue<'rt>,
+        _rt: &mut RuntimeHandle<'rt>,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("cannot set properties of the reference registry")
+    }
+
+    fn properties(&mut self, _rt: &mut RuntimeHandle<'rt>) -> Vec<PropName<'rt>> {
+        Vec::new()
+    }
+}
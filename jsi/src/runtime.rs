@@ -1,7 +1,13 @@
+use crate::function::JsiFn;
+use crate::instrumentation::HeapInfo;
 use crate::object::JsiObject;
-use crate::sys;
+use crate::operations::type_error;
+use crate::script::{self, EvaluateError, PreparedScript};
+use crate::{sys, IntoValue, JsError, JsiBuffer, JsiValue, PropName};
 use std::cell::Cell;
+use std::io::{self, Write};
 use std::marker::PhantomData;
+use std::path::Path;
 use std::pin::Pin;
 
 #[derive(Debug)]
@@ -27,6 +33,228 @@ impl<'rt> RuntimeHandle<'rt> {
         JsiObject(sys::Runtime_global(self.get_inner_mut()), PhantomData)
     }
 
+    /// Evaluates `source` as a script and returns the value of its last
+    /// statement. `source_url` is used by the runtime in stack traces and
+    /// error messages.
+    ///
+    /// Unlike the raw `sys::Runtime::evaluate_javascript`, exceptions thrown
+    /// by the script are returned as an [`EvaluateError`] instead of aborting
+    /// the process.
+    pub fn evaluate(
+        &mut self,
+        source: &str,
+        source_url: &str,
+    ) -> Result<JsiValue<'rt>, EvaluateError<'rt>> {
+        let buffer = sys::Buffer_fromUtf8(source);
+        script::evaluate_buffer(&buffer, source_url, self)
+    }
+
+    /// Like [`RuntimeHandle::evaluate`], but takes the script from a
+    /// [`JsiBuffer`] so that it does not need to be copied. The buffer may
+    /// also contain Hermes bytecode if this is a Hermes runtime.
+    pub fn evaluate_buffer(
+        &mut self,
+        buffer: &JsiBuffer,
+        source_url: &str,
+    ) -> Result<JsiValue<'rt>, EvaluateError<'rt>> {
+        script::evaluate_buffer(&buffer.0, source_url, self)
+    }
+
+    /// Parses a script ahead of time so that it can be evaluated repeatedly
+    /// using [`PreparedScript::evaluate`]. See also [`crate::ScriptCache`].
+    pub fn prepare(
+        &mut self,
+        buffer: &JsiBuffer,
+        source_url: &str,
+    ) -> Result<PreparedScript<'rt>, EvaluateError<'rt>> {
+        PreparedScript::new(buffer, source_url, self)
+    }
+
+    /// Runs the jobs in the runtime's microtask queue (ex. promise
+    /// continuations). `max` is a hint for the maximum number of microtasks
+    /// to run; `None` drains the whole queue. Returns `true` if the queue is
+    /// empty afterwards.
+    ///
+    /// If a microtask throws, the exception is returned and the rest of the
+    /// queue is left alone, so this can be called again to continue draining.
+    /// Note that Hermes only has a microtask queue if it was enabled in its
+    /// `RuntimeConfig`.
+    pub fn drain_microtasks(&mut self, max: Option<u32>) -> Result<bool, EvaluateError<'rt>> {
+        let hint = match max {
+            Some(max) => max.min(i32::MAX as u32) as i32,
+            None => -1,
+        };
+
+        let mut thrown = cxx::UniquePtr::null();
+        let drained = sys::Runtime_drainMicrotasks(self.get_inner_mut(), hint, &mut thrown)
+            .map_err(EvaluateError::Native)?;
+
+        if thrown.is_null() {
+            Ok(drained)
+        } else {
+            let thrown = JsiValue(thrown, PhantomData);
+            Err(EvaluateError::from_thrown(thrown, self))
+        }
+    }
+
+    /// Adds `task` to the runtime's microtask queue, like `queueMicrotask()`
+    /// in JavaScript. Falls back to `Promise.resolve().then()` if the runtime
+    /// does not provide `queueMicrotask`. Fails if neither is usable, for
+    /// example because a script replaced `globalThis.Promise`.
+    pub fn queue_microtask<F>(&mut self, task: F) -> Result<(), JsError<'rt>>
+    where
+        F: 'rt + FnOnce(&mut RuntimeHandle<'rt>) -> anyhow::Result<()>,
+    {
+        let mut task = Some(task);
+
+        let callback = JsiFn::from_host_fn(
+            &PropName::new("microtask", self),
+            0,
+            Box::new(move |_this, _args, rt| {
+                match task.take() {
+                    Some(task) => task(rt)?,
+                    None => anyhow::bail!("microtask is only supposed to be run once!"),
+                }
+
+                Ok(JsiValue::new_undefined())
+            }),
+            self,
+        );
+        let callback = callback.into_value(self);
+
+        let global = self.global();
+        let queue_microtask = global.try_get(PropName::new("queueMicrotask", self), self)?;
+
+        if let Some(queue_microtask) = queue_microtask.try_into_js::<JsiFn>(self) {
+            queue_microtask.call(std::iter::once(callback), self)?;
+            return Ok(());
+        }
+
+        let promise_ctor: JsiObject = global
+            .try_get(PropName::new("Promise", self), self)?
+            .try_into_js(self)
+            .ok_or_else(|| type_error("Promise is not an object", self))?;
+        let resolve: JsiFn = promise_ctor
+            .try_get(PropName::new("resolve", self), self)?
+            .try_into_js(self)
+            .ok_or_else(|| type_error("Promise.resolve is not a function", self))?;
+        let promise: JsiObject = resolve
+            .call_with_this(&promise_ctor, std::iter::empty(), self)?
+            .try_into_js(self)
+            .ok_or_else(|| type_error("Promise.resolve did not return an object", self))?;
+        let then: JsiFn = promise
+            .try_get(PropName::new("then", self), self)?
+            .try_into_js(self)
+            .ok_or_else(|| type_error("Promise.prototype.then is not a function", self))?;
+        then.call_with_this(&promise, std::iter::once(callback), self)?;
+
+        Ok(())
+    }
+
+    /// Runs `f` inside of a `jsi::Scope`, which advises the runtime that
+    /// resources allocated by `f` can be recycled as soon as it returns
+    /// instead of at the next garbage collection. Values created inside of the
+    /// scope (including the one returned by `f`) stay valid afterwards.
+    ///
+    /// This is only a hint, and some runtimes ignore it. Use it around the
+    /// body of loops which create many temporary values.
+    ///
+    /// ```no_run
+    /// # use jsi::{JsiArray, JsiValue, RuntimeHandle};
+    /// # fn f(rt: &mut RuntimeHandle) {
+    /// let mut arr = JsiArray::new(50_000, rt);
+    /// for i in 0..50_000 {
+    ///     rt.scope(|rt| {
+    ///         let item = JsiValue::new_string(&i.to_string(), rt);
+    ///         arr.set(i, &item, rt);
+    ///     });
+    /// }
+    /// # }
+    /// ```
+    pub fn scope<T>(&mut self, f: impl FnOnce(&mut RuntimeHandle<'rt>) -> T) -> T {
+        // dropped after f returns (or panics), which pops the scope
+        let _scope = sys::Scope_create(self.get_inner_mut());
+        f(self)
+    }
+
+    /// Returns statistics about the runtime's garbage-collected heap.
+    pub fn heap_info(&mut self) -> HeapInfo {
+        let mut keys = cxx::CxxVector::new();
+        let mut values = cxx::CxxVector::new();
+
+        sys::Instrumentation_getHeapInfo(
+            self.get_inner_mut().instrumentation(),
+            false,
+            keys.pin_mut(),
+            values.pin_mut(),
+        );
+
+        let raw = keys
+            .iter()
+            .map(|key| key.to_string_lossy().into_owned())
+            .zip(values.iter().copied())
+            .collect();
+
+        HeapInfo::from_raw(raw)
+    }
+
+    /// Forces a full garbage collection. `cause` is a description of why the
+    /// collection was requested, which the runtime may include in its GC logs.
+    ///
+    /// Host objects which are no longer reachable from JS are normally dropped
+    /// during the collection, which makes this useful for leak tests.
+    pub fn collect_garbage(&mut self, cause: &str) {
+        sys::Instrumentation_collectGarbage(self.get_inner_mut().instrumentation(), cause);
+    }
+
+    /// Writes a snapshot of the runtime's heap to the file at `path`. The
+    /// snapshot is in the format used by Chrome DevTools, so it can be opened
+    /// in the Memory tab if the file has a `.heapsnapshot` extension.
+    pub fn create_heap_snapshot_to_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref().to_string_lossy();
+
+        sys::Instrumentation_createSnapshotToFile(self.get_inner_mut().instrumentation(), &path)
+            .map_err(|err| io::Error::other(err.what().to_string()))
+    }
+
+    /// Writes a snapshot of the runtime's heap to `writer`, in the same format
+    /// as [`RuntimeHandle::create_heap_snapshot_to_file`]. The snapshot is
+    /// streamed to the writer as it is created.
+    pub fn create_heap_snapshot_to_writer<W: Write>(&mut self, writer: W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+        let mut error = None;
+
+        let mut callback: sys::WriteCallback = Box::new(|bytes| {
+            // a panicking writer fails the write instead of unwinding into C++
+            let res = sys::catch_panic("writer", || writer.write_all(bytes))
+                .unwrap_or_else(|panic| Err(io::Error::other(panic)));
+
+            match res {
+                Ok(()) => true,
+                Err(err) => {
+                    // the C++ stream stops writing after the first failure
+                    error.get_or_insert(err);
+                    false
+                }
+            }
+        });
+
+        let res = unsafe {
+            sys::Instrumentation_createSnapshotToStream(
+                self.get_inner_mut().instrumentation(),
+                &mut callback as *mut sys::WriteCallback as *mut _,
+            )
+        };
+        drop(callback);
+
+        if let Some(err) = error {
+            return Err(err);
+        }
+
+        res.map_err(|err| io::Error::other(err.what().to_string()))?;
+        writer.flush()
+    }
+
     pub fn eq<T: RuntimeEq>(&mut self, lhs: &T, rhs: &T) -> bool {
         lhs.eq(rhs, self)
     }
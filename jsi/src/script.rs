@@ -0,0 +1,256 @@
+//! # Scripts
+//!
+//! Types used when evaluating JavaScript source code from Rust.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use thiserror::Error;
+
+use crate::{sys, JsError, JsiBuffer, JsiValue, RuntimeHandle};
+
+/// An error that occurred while preparing or evaluating a script with
+/// [`RuntimeHandle::evaluate`] or [`PreparedScript`].
+#[derive(Error, Debug)]
+pub enum EvaluateError<'rt> {
+    /// The script could not be compiled.
+    #[error(transparent)]
+    Syntax(#[from] SyntaxError),
+
+    /// The script threw an exception while it was running. Engines which
+    /// report compilation failures by throwing a `SyntaxError` object (instead
+    /// of failing natively like Hermes does) will also produce this variant.
+    #[error("script threw an exception: {0}")]
+    Thrown(JsError<'rt>),
+
+    /// The runtime failed for a reason other than a JavaScript exception.
+    #[error(transparent)]
+    Native(cxx::Exception),
+}
+
+impl<'rt> EvaluateError<'rt> {
+    pub(crate) fn from_native(err: cxx::Exception, source_url: &str) -> Self {
+        match SyntaxError::parse(err.what(), source_url) {
+            Some(err) => EvaluateError::Syntax(err),
+            None => EvaluateError::Native(err),
+        }
+    }
+
+    pub(crate) fn from_thrown(value: JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Self {
+        EvaluateError::Thrown(JsError::from_thrown(value, rt))
+    }
+}
+
+/// A script failed to compile.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{source_url}:{line}:{column}: {message}")]
+pub struct SyntaxError {
+    /// The source URL that was passed to the runtime along with the script.
+    pub source_url: String,
+    /// The line where the error occurred, starting from 1.
+    pub line: u32,
+    /// The column where the error occurred, starting from 1.
+    pub column: u32,
+    /// The message reported by the compiler.
+    pub message: String,
+}
+
+impl SyntaxError {
+    /// Hermes reports compilation failures as a native exception with a
+    /// message like `Compiling JS failed: 1:5:';' expected`.
+    pub(crate) fn parse(what: &str, source_url: &str) -> Option<Self> {
+        let (_, details) = what.split_once("Compiling JS failed:")?;
+        let bytes = details.as_bytes();
+
+        for start in 0..bytes.len() {
+            if !bytes[start].is_ascii_digit() || (start > 0 && bytes[start - 1].is_ascii_digit()) {
+                continue;
+            }
+
+            let mut parts = details[start..].splitn(3, ':');
+            let line = parts.next().and_then(|s| s.parse().ok());
+            let column = parts.next().and_then(|s| s.parse().ok());
+            let message = parts.next();
+
+            if let (Some(line), Some(column), Some(message)) = (line, column, message) {
+                let message = message.lines().next().unwrap_or("").trim();
+                let message = message.strip_prefix("error:").unwrap_or(message).trim();
+
+                return Some(SyntaxError {
+                    source_url: source_url.to_string(),
+                    line,
+                    column,
+                    message: message.to_string(),
+                });
+            }
+        }
+
+        None
+    }
+}
+
+pub(crate) fn evaluate_buffer<'rt>(
+    buffer: &cxx::SharedPtr<sys::Buffer>,
+    source_url: &str,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<JsiValue<'rt>, EvaluateError<'rt>> {
+    let mut thrown = cxx::UniquePtr::null();
+
+    let value =
+        sys::Runtime_evaluateJavaScript(rt.get_inner_mut(), buffer, source_url, &mut thrown);
+
+    completion(value, thrown, source_url, rt)
+}
+
+/// Converts the outcome of evaluating a script into a `Result`.
+fn completion<'rt>(
+    value: Result<cxx::UniquePtr<sys::JsiValue>, cxx::Exception>,
+    thrown: cxx::UniquePtr<sys::JsiValue>,
+    source_url: &str,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Result<JsiValue<'rt>, EvaluateError<'rt>> {
+    let value = value.map_err(|err| EvaluateError::from_native(err, source_url))?;
+
+    if thrown.is_null() {
+        Ok(JsiValue(value, PhantomData))
+    } else {
+        let thrown = JsiValue(thrown, PhantomData);
+        Err(EvaluateError::from_thrown(thrown, rt))
+    }
+}
+
+/// A script which has been parsed (and compiled to bytecode, if the runtime
+/// does that) ahead of time, so that it can be evaluated many times without
+/// being parsed again. Create one with [`RuntimeHandle::prepare`].
+#[derive(Clone)]
+pub struct PreparedScript<'rt> {
+    pub(crate) inner: cxx::SharedPtr<sys::ConstPreparedJavaScript>,
+    source_url: String,
+    _rt: PhantomData<&'rt mut ()>,
+}
+
+impl<'rt> PreparedScript<'rt> {
+    pub fn new(
+        buffer: &JsiBuffer,
+        source_url: &str,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, EvaluateError<'rt>> {
+        let inner = sys::Runtime_prepareJavaScript(rt.get_inner_mut(), &buffer.0, source_url)
+            .map_err(|err| EvaluateError::from_native(err, source_url))?;
+
+        Ok(PreparedScript {
+            inner,
+            source_url: source_url.to_string(),
+            _rt: PhantomData,
+        })
+    }
+
+    pub fn source_url(&self) -> &str {
+        &self.source_url
+    }
+
+    pub fn evaluate(
+        &self,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<JsiValue<'rt>, EvaluateError<'rt>> {
+        let mut thrown = cxx::UniquePtr::null();
+
+        let value =
+            sys::Runtime_evaluatePreparedJavaScript(rt.get_inner_mut(), &self.inner, &mut thrown);
+
+        completion(value, thrown, &self.source_url, rt)
+    }
+}
+
+/// A cache of [`PreparedScript`]s keyed by a hash of their source, so that
+/// helper scripts which are run over and over (ex. every time a module is
+/// initialized) only need to be parsed once.
+///
+/// Prepared scripts belong to the runtime that prepared them, so keep one
+/// cache per runtime and drop it when that runtime is destroyed (ex. when the
+/// bundle is reloaded).
+pub struct ScriptCache<'rt> {
+    scripts: HashMap<u64, CachedScript<'rt>>,
+}
+
+struct CachedScript<'rt> {
+    buffer: JsiBuffer,
+    script: PreparedScript<'rt>,
+}
+
+impl<'rt> ScriptCache<'rt> {
+    pub fn new() -> Self {
+        ScriptCache {
+            scripts: HashMap::new(),
+        }
+    }
+
+    /// Returns the prepared version of `buffer`, preparing it if this cache
+    /// has not seen a script with the same contents and source URL before.
+    pub fn prepare(
+        &mut self,
+        buffer: &JsiBuffer,
+        source_url: &str,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<PreparedScript<'rt>, EvaluateError<'rt>> {
+        let key = Self::key(buffer, source_url);
+
+        if let Some(cached) = self.scripts.get(&key) {
+            // guard against hash collisions
+            if cached.script.source_url == source_url
+                && cached.buffer.as_bytes() == buffer.as_bytes()
+            {
+                return Ok(cached.script.clone());
+            }
+        }
+
+        let script = PreparedScript::new(buffer, source_url, rt)?;
+
+        self.scripts.insert(
+            key,
+            CachedScript {
+                buffer: buffer.clone(),
+                script: script.clone(),
+            },
+        );
+
+        Ok(script)
+    }
+
+    /// Prepares `buffer` using the cache and then evaluates it.
+    pub fn evaluate(
+        &mut self,
+        buffer: &JsiBuffer,
+        source_url: &str,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<JsiValue<'rt>, EvaluateError<'rt>> {
+        self.prepare(buffer, source_url, rt)?.evaluate(rt)
+    }
+
+    pub fn len(&self) -> usize {
+        self.scripts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.scripts.clear()
+    }
+
+    fn key(buffer: &JsiBuffer, source_url: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        source_url.hash(&mut hasher);
+        buffer.as_bytes().hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<'rt> Default for ScriptCache<'rt> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
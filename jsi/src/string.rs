@@ -12,6 +12,28 @@ impl<'rt> JsiString<'rt> {
     pub fn new(name: &str, rt: &mut RuntimeHandle<'rt>) -> Self {
         JsiString(sys::String_fromUtf8(rt.get_inner_mut(), name), PhantomData)
     }
+
+    /// Creates a string from UTF-16 code units. Lone surrogates are kept
+    /// as they are, since JS strings are not required to be valid UTF-16.
+    pub fn from_utf16(units: &[u16], rt: &mut RuntimeHandle<'rt>) -> Self {
+        JsiString(
+            sys::String_fromUtf16(rt.get_inner_mut(), units),
+            PhantomData,
+        )
+    }
+
+    /// The UTF-16 code units of this string. Unlike the UTF-8 conversion
+    /// used by [`RuntimeDisplay`], this is lossless. Indices into the
+    /// result are the same as string indices in JS.
+    pub fn to_utf16(&self, rt: &mut RuntimeHandle<'rt>) -> Vec<u16> {
+        sys::String_toUtf16(&*self.0, rt.get_inner_mut())
+    }
+
+    /// The length of this string in UTF-16 code units, which is the same as
+    /// its `length` property in JS.
+    pub fn len_utf16(&self, rt: &mut RuntimeHandle<'rt>) -> usize {
+        self.to_utf16(rt).len()
+    }
 }
 
 impl RuntimeEq for JsiString<'_> {
@@ -32,9 +54,7 @@ impl RuntimeDisplay for JsiString<'_> {
 
 impl<'rt> RuntimeClone<'rt> for JsiString<'rt> {
     fn clone(&self, rt: &mut RuntimeHandle<'rt>) -> Self {
-        let text = self.0.to_string(rt.get_inner_mut());
-        let text = String::from_utf8_lossy(text.as_bytes());
-        Self::new(text.as_ref(), rt)
+        JsiString(sys::String_copy(&*self.0, rt.get_inner_mut()), PhantomData)
     }
 }
 
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use crate::{sys, RuntimeDisplay, RuntimeEq, RuntimeHandle};
+use crate::{sys, JsiFn, JsiObject, JsiValue, PropName, RuntimeDisplay, RuntimeEq, RuntimeHandle};
 
 /// A JavaScript `Symbol`
 pub struct JsiSymbol<'rt>(
@@ -8,6 +8,79 @@ pub struct JsiSymbol<'rt>(
     pub(crate) PhantomData<&'rt ()>,
 );
 
+/// JSI cannot create symbols by itself, so the functions that create or look
+/// up symbols go through `globalThis.Symbol`. Since scripts can replace or
+/// delete it, they return `None` if it is missing or does not behave like the
+/// built-in `Symbol`.
+impl<'rt> JsiSymbol<'rt> {
+    /// Creates a new unique symbol, like `Symbol(description)`.
+    pub fn new(description: Option<&str>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        let ctor = symbol_ctor(rt)?;
+        let args = description.map(|description| JsiValue::new_string(description, rt));
+        call_symbol_fn(&ctor, args, rt)
+    }
+
+    /// Returns the symbol with the given key from the runtime's global symbol
+    /// registry, creating it if it does not exist yet, like `Symbol.for(key)`.
+    pub fn for_key(key: &str, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        let for_key: JsiFn = JsiObject::from(symbol_ctor(rt)?)
+            .try_get(PropName::new("for", rt), rt)
+            .ok()?
+            .try_into_js(rt)?;
+        let key = JsiValue::new_string(key, rt);
+        call_symbol_fn(&for_key, Some(key), rt)
+    }
+
+    /// Returns one of the well-known symbols, ex. `"iterator"` for
+    /// `Symbol.iterator`. Returns `None` if the runtime does not define it.
+    pub fn well_known(name: &str, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        JsiObject::from(symbol_ctor(rt)?)
+            .try_get(PropName::new(name, rt), rt)
+            .ok()?
+            .try_into_js(rt)
+    }
+
+    /// `Symbol.iterator`
+    pub fn iterator(rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        Self::well_known("iterator", rt)
+    }
+
+    /// `Symbol.asyncIterator`
+    pub fn async_iterator(rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        Self::well_known("asyncIterator", rt)
+    }
+
+    /// `Symbol.hasInstance`
+    pub fn has_instance(rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        Self::well_known("hasInstance", rt)
+    }
+
+    /// `Symbol.toPrimitive`
+    pub fn to_primitive(rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        Self::well_known("toPrimitive", rt)
+    }
+
+    /// `Symbol.toStringTag`
+    pub fn to_string_tag(rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+        Self::well_known("toStringTag", rt)
+    }
+}
+
+fn symbol_ctor<'rt>(rt: &mut RuntimeHandle<'rt>) -> Option<JsiFn<'rt>> {
+    rt.global()
+        .try_get(PropName::new("Symbol", rt), rt)
+        .ok()?
+        .try_into_js(rt)
+}
+
+fn call_symbol_fn<'rt>(
+    f: &JsiFn<'rt>,
+    arg: Option<JsiValue<'rt>>,
+    rt: &mut RuntimeHandle<'rt>,
+) -> Option<JsiSymbol<'rt>> {
+    f.call(arg, rt).ok()?.try_into_js(rt)
+}
+
 impl RuntimeEq for JsiSymbol<'_> {
     fn eq(&self, other: &Self, rt: &mut RuntimeHandle<'_>) -> bool {
         sys::Symbol_compare(
@@ -3,13 +3,16 @@ use std::marker::PhantomData;
 
 use crate::array::JsiArray;
 use crate::array_buffer::JsiArrayBuffer;
+use crate::bigint::JsiBigInt;
 use crate::function::JsiFn;
+use crate::integer::safe_number;
 use crate::object::JsiObject;
 use crate::string::JsiString;
 use crate::symbol::JsiSymbol;
 use crate::{
-    sys, FromObject, IntoObject, OwnedJsiHostObject, OwnedJsiUserHostObject, RuntimeClone,
-    RuntimeDisplay, RuntimeEq, RuntimeHandle, SharedJsiHostObject, SharedJsiUserHostObject,
+    sys, ConversionError, FromObject, IntegerConversionError, IntoObject, OwnedJsiHostObject,
+    OwnedJsiUserHostObject, RuntimeClone, RuntimeDisplay, RuntimeEq, RuntimeHandle,
+    SharedJsiHostObject, SharedJsiUserHostObject, TryFromValue,
 };
 
 pub struct JsiValue<'rt>(
@@ -22,14 +25,26 @@ impl<'rt> JsiValue<'rt> {
         Self(sys::Value_fromUndefined(), PhantomData)
     }
 
+    /// Creates `null`. `Option::None` is also converted to `null`.
     pub fn new_null() -> Self {
-        Self(sys::Value_fromUndefined(), PhantomData)
+        Self(sys::Value_fromNull(), PhantomData)
     }
 
     pub fn new_number(n: f64) -> Self {
         Self(sys::Value_fromDouble(n), PhantomData)
     }
 
+    /// Creates a number from an integer, or fails if it is outside of
+    /// `Number.MIN_SAFE_INTEGER..=Number.MAX_SAFE_INTEGER`, where it would
+    /// be rounded. [`IntoValue`] rounds such integers instead.
+    pub fn new_safe_integer<T: TryInto<i128> + std::fmt::Display + Copy>(
+        n: T,
+    ) -> Result<Self, IntegerConversionError> {
+        safe_number(n)
+            .map(Self::new_number)
+            .ok_or_else(|| IntegerConversionError::Unsafe(n.to_string()))
+    }
+
     pub fn new_bool(b: bool) -> Self {
         Self(sys::Value_fromBool(b), PhantomData)
     }
@@ -67,20 +82,41 @@ impl<'rt> JsiValue<'rt> {
         self.0.is_symbol()
     }
 
+    pub fn is_bigint(&self) -> bool {
+        self.0.is_bigint()
+    }
+
     pub fn is_object(&self) -> bool {
         self.0.is_object()
     }
 
-    pub fn is_truthy(&self, rt: &mut RuntimeHandle<'rt>) -> bool {
-        match self.kind(rt) {
-            JsiValueKind::Undefined | JsiValueKind::Null => false,
-            JsiValueKind::Number(n) => n.abs() > f64::EPSILON,
-            JsiValueKind::Bool(b) => b,
-            JsiValueKind::String(s) => rt.display(&s).to_string().len() > 0,
-            JsiValueKind::Symbol(_) | JsiValueKind::Object(_) => true,
+    /// The name of the type of this value for error messages, like `typeof`
+    /// except that `null` is `"null"`.
+    pub(crate) fn type_name(&self) -> &'static str {
+        if self.is_undefined() {
+            "undefined"
+        } else if self.is_null() {
+            "null"
+        } else if self.is_bool() {
+            "boolean"
+        } else if self.is_number() {
+            "number"
+        } else if self.is_string() {
+            "string"
+        } else if self.is_symbol() {
+            "symbol"
+        } else if self.is_bigint() {
+            "bigint"
+        } else {
+            "object"
         }
     }
 
+    /// Same as [`JsiValue::to_boolean`].
+    pub fn is_truthy(&self, rt: &mut RuntimeHandle<'rt>) -> bool {
+        self.to_boolean(rt)
+    }
+
     pub fn to_js_string(&self, rt: &mut RuntimeHandle<'rt>) -> JsiString<'rt> {
         JsiString(self.0.to_string(rt.get_inner_mut()), PhantomData)
     }
@@ -98,6 +134,8 @@ impl<'rt> JsiValue<'rt> {
             JsiValueKind::String(FromValue::from_value(self, rt).unwrap())
         } else if self.is_symbol() {
             JsiValueKind::Symbol(FromValue::from_value(self, rt).unwrap())
+        } else if self.is_bigint() {
+            JsiValueKind::BigInt(FromValue::from_value(self, rt).unwrap())
         } else if self.is_object() {
             JsiValueKind::Object(FromValue::from_value(self, rt).unwrap())
         } else {
@@ -112,6 +150,14 @@ impl<'rt> JsiValue<'rt> {
     pub fn into_js<T: FromValue<'rt>>(&self, rt: &mut RuntimeHandle<'rt>) -> T {
         self.try_into_js(rt).unwrap()
     }
+
+    /// Like [`JsiValue::try_into_js`], but says why the conversion failed.
+    pub fn convert<T: TryFromValue<'rt>>(
+        &self,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<T, ConversionError> {
+        T::try_from_value(self, rt)
+    }
 }
 
 unsafe impl<'rt> Send for JsiValue<'rt> {}
@@ -161,63 +207,131 @@ pub trait AsValue<'rt> {
     fn as_value(&self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt>;
 }
 
+/// Conversion from [`JsiValue`] which discards the reason for a failure. This
+/// is implemented for every type that implements [`TryFromValue`].
 pub trait FromValue<'rt>: Sized {
     fn from_value(value: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self>;
 }
 
-impl<'rt> FromValue<'rt> for JsiValue<'rt> {
+impl<'rt, T: TryFromValue<'rt>> FromValue<'rt> for T {
     fn from_value(value: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
-        Some(rt.clone(value))
+        T::try_from_value(value, rt).ok()
     }
 }
 
-impl<'rt> FromValue<'rt> for f64 {
-    fn from_value(value: &JsiValue<'rt>, _rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
-        value.0.get_number().ok()
+/// `null` and `undefined` are converted to `None`.
+impl<'rt, T: TryFromValue<'rt>> TryFromValue<'rt> for Option<T> {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        if value.is_null() || value.is_undefined() {
+            Ok(None)
+        } else {
+            T::try_from_value(value, rt).map(Some)
+        }
     }
 }
 
-impl<'rt> FromValue<'rt> for bool {
-    fn from_value(value: &JsiValue<'rt>, _rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
-        value.0.get_bool().ok()
+impl<'rt> TryFromValue<'rt> for JsiValue<'rt> {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        Ok(rt.clone(value))
     }
 }
 
-impl<'rt> FromValue<'rt> for JsiObject<'rt> {
-    fn from_value(value: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
-        sys::Value_asObject(&*value.0, rt.get_inner_mut())
-            .ok()
-            .map(|raw| JsiObject(raw, PhantomData))
+impl<'rt> TryFromValue<'rt> for f64 {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        value
+            .0
+            .get_number()
+            .map_err(|_| ConversionError::new("number", value, rt))
     }
 }
 
-impl<'rt, T: FromObject<'rt>> FromValue<'rt> for T {
-    fn from_value(value: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
+impl<'rt> TryFromValue<'rt> for bool {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        value
+            .0
+            .get_bool()
+            .map_err(|_| ConversionError::new("boolean", value, rt))
+    }
+}
+
+impl<'rt> TryFromValue<'rt> for JsiObject<'rt> {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        match sys::Value_asObject(&*value.0, rt.get_inner_mut()) {
+            Ok(raw) => Ok(JsiObject(raw, PhantomData)),
+            Err(_) => Err(ConversionError::new("object", value, rt)),
+        }
+    }
+}
+
+impl<'rt, T: FromObject<'rt>> TryFromValue<'rt> for T {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
         let obj: Option<JsiObject> = FromValue::from_value(value, rt);
         obj.and_then(|obj| FromObject::from_object(&obj, rt))
+            .ok_or_else(|| ConversionError::new(T::TYPE_NAME, value, rt))
     }
 }
 
-impl<'rt> FromValue<'rt> for String {
-    fn from_value(value: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
-        let s: Option<JsiString> = FromValue::from_value(value, rt);
-        s.map(|s| rt.to_string(&s))
+impl<'rt> TryFromValue<'rt> for String {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        let s: JsiString = TryFromValue::try_from_value(value, rt)?;
+        Ok(rt.to_string(&s))
     }
 }
 
-impl<'rt> FromValue<'rt> for JsiString<'rt> {
-    fn from_value(value: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
-        sys::Value_asString(&*value.0, rt.get_inner_mut())
-            .ok()
-            .map(|raw| JsiString(raw, PhantomData))
+impl<'rt> TryFromValue<'rt> for JsiString<'rt> {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        match sys::Value_asString(&*value.0, rt.get_inner_mut()) {
+            Ok(raw) => Ok(JsiString(raw, PhantomData)),
+            Err(_) => Err(ConversionError::new("string", value, rt)),
+        }
     }
 }
 
-impl<'rt> FromValue<'rt> for JsiSymbol<'rt> {
-    fn from_value(value: &JsiValue<'rt>, rt: &mut RuntimeHandle<'rt>) -> Option<Self> {
-        sys::Value_asSymbol(&*value.0, rt.get_inner_mut())
-            .ok()
-            .map(|raw| JsiSymbol(raw, PhantomData))
+impl<'rt> TryFromValue<'rt> for JsiSymbol<'rt> {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        match sys::Value_asSymbol(&*value.0, rt.get_inner_mut()) {
+            Ok(raw) => Ok(JsiSymbol(raw, PhantomData)),
+            Err(_) => Err(ConversionError::new("symbol", value, rt)),
+        }
+    }
+}
+
+impl<'rt> TryFromValue<'rt> for JsiBigInt<'rt> {
+    fn try_from_value(
+        value: &JsiValue<'rt>,
+        rt: &mut RuntimeHandle<'rt>,
+    ) -> Result<Self, ConversionError> {
+        match sys::Value_asBigInt(&*value.0, rt.get_inner_mut()) {
+            Ok(raw) => Ok(JsiBigInt(raw, PhantomData)),
+            Err(_) => Err(ConversionError::new("bigint", value, rt)),
+        }
     }
 }
 
@@ -251,12 +365,6 @@ impl<'rt> IntoValue<'rt> for f64 {
     }
 }
 
-impl<'rt> IntoValue<'rt> for usize {
-    fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
-        IntoValue::into_value(self as f64, rt)
-    }
-}
-
 impl<'rt> IntoValue<'rt> for u8 {
     fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
         IntoValue::into_value(self as f64, rt)
@@ -275,18 +383,6 @@ impl<'rt> IntoValue<'rt> for u32 {
     }
 }
 
-impl<'rt> IntoValue<'rt> for u64 {
-    fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
-        IntoValue::into_value(self as f64, rt)
-    }
-}
-
-impl<'rt> IntoValue<'rt> for isize {
-    fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
-        IntoValue::into_value(self as f64, rt)
-    }
-}
-
 impl<'rt> IntoValue<'rt> for i8 {
     fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
         IntoValue::into_value(self as f64, rt)
@@ -305,12 +401,26 @@ impl<'rt> IntoValue<'rt> for i32 {
     }
 }
 
-impl<'rt> IntoValue<'rt> for i64 {
-    fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
-        IntoValue::into_value(self as f64, rt)
-    }
+macro_rules! impl_into_number {
+    ($($ty: ty),*) => {
+        $(
+            /// Creates a number. Values outside of
+            /// `Number.MIN_SAFE_INTEGER..=Number.MAX_SAFE_INTEGER` are
+            /// rounded to the nearest number; use
+            /// [`JsiValue::new_safe_integer`] to fail instead, or wrap the
+            /// value in a [`BigIntValue`](crate::BigIntValue) to create a
+            /// `BigInt`.
+            impl<'rt> IntoValue<'rt> for $ty {
+                fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+                    IntoValue::into_value(self as f64, rt)
+                }
+            }
+        )*
+    };
 }
 
+impl_into_number!(i64, i128, isize, u64, u128, usize);
+
 impl<'rt> IntoValue<'rt> for () {
     fn into_value(self, _rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
         JsiValue::new_undefined()
@@ -365,6 +475,15 @@ impl<'rt> IntoValue<'rt> for JsiSymbol<'rt> {
     }
 }
 
+impl<'rt> IntoValue<'rt> for JsiBigInt<'rt> {
+    fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        JsiValue(
+            sys::Value_fromBigInt(rt.get_inner_mut(), self.0),
+            PhantomData,
+        )
+    }
+}
+
 impl<'rt> IntoValue<'rt> for JsiArray<'rt> {
     fn into_value(self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
         let obj: JsiObject = self.into();
@@ -450,6 +569,15 @@ impl<'rt> AsValue<'rt> for JsiSymbol<'rt> {
     }
 }
 
+impl<'rt> AsValue<'rt> for JsiBigInt<'rt> {
+    fn as_value(&self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
+        JsiValue(
+            sys::Value_copyFromBigInt(rt.get_inner_mut(), self.0.as_ref().unwrap()),
+            PhantomData,
+        )
+    }
+}
+
 impl<'rt> AsValue<'rt> for JsiArray<'rt> {
     fn as_value(&self, rt: &mut RuntimeHandle<'rt>) -> JsiValue<'rt> {
         // this is a subclass of JsiObject, so pointer cast is safe
@@ -493,5 +621,6 @@ pub enum JsiValueKind<'rt> {
     Bool(bool),
     String(JsiString<'rt>),
     Symbol(JsiSymbol<'rt>),
+    BigInt(JsiBigInt<'rt>),
     Object(JsiObject<'rt>),
 }
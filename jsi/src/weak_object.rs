@@ -0,0 +1,34 @@
+use std::marker::PhantomData;
+
+use crate::{sys, JsiObject, RuntimeHandle};
+
+/// A weak reference to a JavaScript `Object`, which does not keep the object
+/// from being garbage collected. Created with [`JsiObject::downgrade`].
+pub struct JsiWeakObject<'rt>(
+    pub(crate) cxx::UniquePtr<sys::JsiWeakObject>,
+    pub(crate) PhantomData<&'rt mut ()>,
+);
+
+impl<'rt> JsiWeakObject<'rt> {
+    /// Returns the object if it has not been garbage collected yet.
+    pub fn upgrade(&mut self, rt: &mut RuntimeHandle<'rt>) -> Option<JsiObject<'rt>> {
+        let value = self.0.pin_mut().lock(rt.get_inner_mut());
+
+        match sys::Value_asObject(&*value, rt.get_inner_mut()) {
+            Ok(raw) => Some(JsiObject(raw, PhantomData)),
+            Err(_) => None,
+        }
+    }
+}
+
+impl<'rt> JsiObject<'rt> {
+    /// Creates a weak reference to this object.
+    pub fn downgrade(&self, rt: &mut RuntimeHandle<'rt>) -> JsiWeakObject<'rt> {
+        JsiWeakObject(
+            sys::JsiWeakObject::from_object(rt.get_inner_mut(), &*self.0),
+            PhantomData,
+        )
+    }
+}
+
+unsafe impl<'rt> Send for JsiWeakObject<'rt> {}